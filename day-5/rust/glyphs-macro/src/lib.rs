@@ -0,0 +1,792 @@
+// The glyphs! macro used by letters.rs.
+//
+// It expands to a Font (metrics, a HashMap<char, Glyph>, ligatures,
+// alternates and kerning) and checks the definitions while compiling: duplicate
+// characters, x outside 0-100, y outside descender..=ascender, metrics out of
+// order, radii that aren't positive, polylines with a single point, width
+// restrictions over 100, unknown or repeated anchors, repeated or single char
+// ligatures, alternates for chars without a glyph and repeated or out of range
+// kerning are errors pointing at the offending token.
+use std::collections::HashMap;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::{parenthesized, parse_macro_input, LitChar, LitInt, Token};
+
+mod kw {
+    syn::custom_keyword!(line);
+    syn::custom_keyword!(arc);
+    syn::custom_keyword!(quad);
+    syn::custom_keyword!(cubic);
+    syn::custom_keyword!(poly);
+    syn::custom_keyword!(via);
+    syn::custom_keyword!(radius);
+    syn::custom_keyword!(from);
+    syn::custom_keyword!(restrict_width);
+    syn::custom_keyword!(anchor);
+    syn::custom_keyword!(ligature);
+    syn::custom_keyword!(alternate);
+    syn::custom_keyword!(before);
+    syn::custom_keyword!(after);
+    syn::custom_keyword!(kern);
+    syn::custom_keyword!(metrics);
+    syn::custom_keyword!(baseline);
+    syn::custom_keyword!(x_height);
+    syn::custom_keyword!(cap_height);
+    syn::custom_keyword!(ascender);
+    syn::custom_keyword!(descender);
+    syn::custom_keyword!(line_gap);
+}
+
+// Glyphs are 100 units wide, ligatures 100 per char, the vertical range comes
+// from the metrics
+const COORD_RANGE: std::ops::RangeInclusive<i64> = 0..=100;
+
+// Anchor names and the Anchor variants they become
+const ANCHORS: [(&str, &str); 5] = [
+    ("top", "Top"),
+    ("bottom", "Bottom"),
+    ("above_right", "AboveRight"),
+    ("entry", "Entry"),
+    ("exit", "Exit"),
+];
+
+// Same as FontMetrics::default(), in field order
+const DEFAULT_METRICS: [(&str, i64); 6] = [
+    ("baseline", 0),
+    ("x_height", 100),
+    ("cap_height", 100),
+    ("ascender", 100),
+    ("descender", 0),
+    ("line_gap", 25),
+];
+
+// An integer literal with an optional leading minus
+struct Num {
+    value: i64,
+    tokens: TokenStream2,
+}
+
+impl Parse for Num {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let minus: Option<Token![-]> = input.parse()?;
+        let lit: LitInt = input
+            .parse()
+            .map_err(|err| syn::Error::new(err.span(), "expected an integer literal"))?;
+        let value = lit.base10_parse::<i64>()?;
+
+        let mut tokens = minus.to_token_stream();
+        lit.to_tokens(&mut tokens);
+        Ok(Self {
+            value: if minus.is_some() { -value } else { value },
+            tokens,
+        })
+    }
+}
+
+impl Num {
+    fn error(&self, message: impl std::fmt::Display) -> syn::Error {
+        syn::Error::new_spanned(&self.tokens, message)
+    }
+
+    fn to_i16(&self) -> syn::Result<i16> {
+        i16::try_from(self.value)
+            .map_err(|_| self.error(format!("`{}` does not fit in an i16", self.value)))
+    }
+}
+
+// `(a)`
+fn parse_value(input: ParseStream) -> syn::Result<Num> {
+    let content;
+    parenthesized!(content in input);
+    content.parse()
+}
+
+// `(x, y)`
+fn parse_point(input: ParseStream) -> syn::Result<(Num, Num)> {
+    let content;
+    parenthesized!(content in input);
+    let x = content.parse()?;
+    content.parse::<Token![,]>()?;
+    let y = content.parse()?;
+    Ok((x, y))
+}
+
+enum StrokeDef {
+    Line {
+        from: (Num, Num),
+        to: (Num, Num),
+    },
+    Arc {
+        center: (Num, Num),
+        radius: Num,
+        start: Num,
+        end: Num,
+    },
+    Quadratic {
+        from: (Num, Num),
+        ctrl: (Num, Num),
+        to: (Num, Num),
+    },
+    Cubic {
+        from: (Num, Num),
+        ctrl1: (Num, Num),
+        ctrl2: (Num, Num),
+        to: (Num, Num),
+    },
+    Polyline {
+        keyword: kw::poly,
+        points: Vec<(Num, Num)>,
+    },
+}
+
+// What a glyph definition is for
+enum GlyphKind {
+    Glyph,
+    // `ligature 'f' 'i' { ... }`, ch is the first of the chars
+    Ligature {
+        keyword: kw::ligature,
+        chars: Vec<LitChar>,
+    },
+    // `alternate 'r' before ('n', 'm') after ('o') { ... }`
+    Alternate {
+        before: Vec<LitChar>,
+        after: Vec<LitChar>,
+    },
+}
+
+struct GlyphDef {
+    kind: GlyphKind,
+    ch: LitChar,
+    strokes: Vec<StrokeDef>,
+    width_restriction: Option<Num>,
+    anchors: Vec<(syn::Ident, (Num, Num))>,
+}
+
+// `('a', 'b')`
+fn parse_chars(input: ParseStream) -> syn::Result<Vec<LitChar>> {
+    let content;
+    parenthesized!(content in input);
+    let chars = content.parse_terminated(|input| input.parse::<LitChar>(), Token![,])?;
+    if chars.is_empty() {
+        return Err(content.error("expected a character"));
+    }
+    Ok(chars.into_iter().collect())
+}
+
+impl Parse for GlyphDef {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let (kind, ch) = if input.peek(kw::ligature) {
+            let keyword = input.parse::<kw::ligature>()?;
+            let mut chars = Vec::new();
+            while input.peek(LitChar) {
+                chars.push(input.parse::<LitChar>()?);
+            }
+            let Some(first) = chars.first().cloned() else {
+                return Err(input.error("expected the characters of the ligature"));
+            };
+            (GlyphKind::Ligature { keyword, chars }, first)
+        } else if input.peek(kw::alternate) {
+            input.parse::<kw::alternate>()?;
+            let ch = input.parse()?;
+            let (mut before, mut after) = (Vec::new(), Vec::new());
+            loop {
+                if input.peek(kw::before) {
+                    input.parse::<kw::before>()?;
+                    before.extend(parse_chars(input)?);
+                } else if input.peek(kw::after) {
+                    input.parse::<kw::after>()?;
+                    after.extend(parse_chars(input)?);
+                } else {
+                    break;
+                }
+            }
+            (GlyphKind::Alternate { before, after }, ch)
+        } else {
+            (GlyphKind::Glyph, input.parse()?)
+        };
+        let body;
+        syn::braced!(body in input);
+
+        let mut strokes = Vec::new();
+        let mut width_restriction = None;
+        let mut anchors = Vec::new();
+        while !body.is_empty() {
+            let lookahead = body.lookahead1();
+            if lookahead.peek(kw::line) {
+                body.parse::<kw::line>()?;
+                let from = parse_point(&body)?;
+                body.parse::<Token![->]>()?;
+                let to = parse_point(&body)?;
+                strokes.push(StrokeDef::Line { from, to });
+            } else if lookahead.peek(kw::arc) {
+                body.parse::<kw::arc>()?;
+                let center = parse_point(&body)?;
+                body.parse::<kw::radius>()?;
+                let radius = parse_value(&body)?;
+                body.parse::<kw::from>()?;
+                let start = parse_value(&body)?;
+                body.parse::<Token![->]>()?;
+                let end = parse_value(&body)?;
+                strokes.push(StrokeDef::Arc {
+                    center,
+                    radius,
+                    start,
+                    end,
+                });
+            } else if lookahead.peek(kw::quad) {
+                body.parse::<kw::quad>()?;
+                let from = parse_point(&body)?;
+                body.parse::<kw::via>()?;
+                let ctrl = parse_point(&body)?;
+                body.parse::<Token![->]>()?;
+                let to = parse_point(&body)?;
+                strokes.push(StrokeDef::Quadratic { from, ctrl, to });
+            } else if lookahead.peek(kw::cubic) {
+                body.parse::<kw::cubic>()?;
+                let from = parse_point(&body)?;
+                body.parse::<kw::via>()?;
+                let ctrl1 = parse_point(&body)?;
+                let ctrl2 = parse_point(&body)?;
+                body.parse::<Token![->]>()?;
+                let to = parse_point(&body)?;
+                strokes.push(StrokeDef::Cubic {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                });
+            } else if lookahead.peek(kw::poly) {
+                let keyword = body.parse::<kw::poly>()?;
+                let mut points = vec![parse_point(&body)?];
+                while body.peek(Token![->]) {
+                    body.parse::<Token![->]>()?;
+                    points.push(parse_point(&body)?);
+                }
+                strokes.push(StrokeDef::Polyline { keyword, points });
+            } else if lookahead.peek(kw::restrict_width) {
+                body.parse::<kw::restrict_width>()?;
+                width_restriction = Some(parse_value(&body)?);
+            } else if lookahead.peek(kw::anchor) {
+                body.parse::<kw::anchor>()?;
+                let name: syn::Ident = body.parse()?;
+                anchors.push((name, parse_point(&body)?));
+            } else {
+                return Err(lookahead.error());
+            }
+        }
+
+        Ok(Self {
+            kind,
+            ch,
+            strokes,
+            width_restriction,
+            anchors,
+        })
+    }
+}
+
+// `metrics { baseline (0) descender (-50) ... }`, every line is optional
+#[derive(Default)]
+struct MetricsDef {
+    values: Vec<(syn::Ident, Num)>,
+}
+
+impl MetricsDef {
+    fn get(&self, name: &str) -> Option<&Num> {
+        self.values
+            .iter()
+            .rev()
+            .find(|(ident, _)| ident == name)
+            .map(|(_, num)| num)
+    }
+
+    fn value(&self, name: &str) -> i64 {
+        match self.get(name) {
+            Some(num) => num.value,
+            None => DEFAULT_METRICS
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, v)| *v)
+                .unwrap(),
+        }
+    }
+}
+
+impl Parse for MetricsDef {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<kw::metrics>()?;
+        let body;
+        syn::braced!(body in input);
+
+        let mut values = Vec::new();
+        while !body.is_empty() {
+            let lookahead = body.lookahead1();
+            if lookahead.peek(kw::baseline)
+                || lookahead.peek(kw::x_height)
+                || lookahead.peek(kw::cap_height)
+                || lookahead.peek(kw::ascender)
+                || lookahead.peek(kw::descender)
+                || lookahead.peek(kw::line_gap)
+            {
+                let name: syn::Ident = body.parse()?;
+                values.push((name, parse_value(&body)?));
+            } else {
+                return Err(lookahead.error());
+            }
+        }
+        Ok(Self { values })
+    }
+}
+
+// `kern 'r' 'y' (-10)`
+struct KernDef {
+    keyword: kw::kern,
+    left: LitChar,
+    right: LitChar,
+    value: Num,
+}
+
+impl Parse for KernDef {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            keyword: input.parse()?,
+            left: input.parse()?,
+            right: input.parse()?,
+            value: parse_value(input)?,
+        })
+    }
+}
+
+struct FontDef {
+    metrics: MetricsDef,
+    glyphs: Vec<GlyphDef>,
+    kerning: Vec<KernDef>,
+}
+
+impl Parse for FontDef {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut metrics: Option<MetricsDef> = None;
+        let mut glyphs = Vec::new();
+        let mut kerning = Vec::new();
+        while !input.is_empty() {
+            if input.peek(kw::kern) {
+                kerning.push(input.parse()?);
+            } else if input.peek(kw::metrics) {
+                let span = input.span();
+                if metrics.is_some() {
+                    return Err(syn::Error::new(span, "metrics are already defined"));
+                }
+                metrics = Some(input.parse()?);
+            } else {
+                glyphs.push(input.parse()?);
+            }
+        }
+        Ok(Self {
+            metrics: metrics.unwrap_or_default(),
+            glyphs,
+            kerning,
+        })
+    }
+}
+
+// Collects every problem so one compile shows all of them
+struct Errors {
+    errors: Option<syn::Error>,
+    x_range: std::ops::RangeInclusive<i64>, // of the glyph being checked
+    y_range: std::ops::RangeInclusive<i64>,
+}
+
+impl Errors {
+    fn push(&mut self, err: syn::Error) {
+        match &mut self.errors {
+            Some(errors) => errors.combine(err),
+            None => self.errors = Some(err),
+        }
+    }
+
+    fn check_point(&mut self, (x, y): &(Num, Num)) {
+        if !self.x_range.contains(&x.value) {
+            self.push(x.error(format!(
+                "x coordinate `{}` is outside the 0-{} glyph box",
+                x.value,
+                self.x_range.end()
+            )));
+        }
+        if !self.y_range.contains(&y.value) {
+            self.push(y.error(format!(
+                "y coordinate `{}` is outside the descender-ascender range {}..={}",
+                y.value,
+                self.y_range.start(),
+                self.y_range.end()
+            )));
+        }
+    }
+}
+
+fn validate(font: &FontDef) -> Result<(), syn::Error> {
+    let metrics = &font.metrics;
+    let mut errors = Errors {
+        errors: None,
+        x_range: COORD_RANGE,
+        y_range: metrics.value("descender")..=metrics.value("ascender"),
+    };
+
+    let mut seen_metrics: HashMap<String, &syn::Ident> = HashMap::new();
+    for (name, num) in &metrics.values {
+        if seen_metrics.insert(name.to_string(), name).is_some() {
+            errors.push(syn::Error::new(
+                name.span(),
+                format!("metric `{name}` is defined more than once"),
+            ));
+        }
+        if let Err(err) = num.to_i16() {
+            errors.push(err);
+        }
+    }
+
+    // descender <= baseline < x_height <= cap_height <= ascender
+    let order = [
+        ("descender", "baseline", false),
+        ("baseline", "x_height", true),
+        ("x_height", "cap_height", false),
+        ("cap_height", "ascender", false),
+    ];
+    for (lower, upper, strict) in order {
+        let (low, high) = (metrics.value(lower), metrics.value(upper));
+        if low > high || (strict && low == high) {
+            let relation = if strict { "above" } else { "at or above" };
+            let message = format!("{upper} ({high}) must be {relation} {lower} ({low})");
+            // Point at whichever of the two was written out
+            if let Some(num) = metrics.get(upper).or_else(|| metrics.get(lower)) {
+                errors.push(num.error(message));
+            }
+        }
+    }
+    if let Some(gap) = metrics.get("line_gap").filter(|gap| gap.value < 0) {
+        errors.push(gap.error("line_gap can't be negative"));
+    }
+
+    let mut seen: HashMap<char, &LitChar> = HashMap::new();
+    let mut seen_ligatures: Vec<String> = Vec::new();
+
+    for glyph in &font.glyphs {
+        let ch = glyph.ch.value();
+        errors.x_range = COORD_RANGE;
+        match &glyph.kind {
+            GlyphKind::Glyph => {
+                if let Some(first) = seen.get(&ch) {
+                    errors.push(syn::Error::new(
+                        glyph.ch.span(),
+                        format!("glyph {ch:?} is defined more than once"),
+                    ));
+                    errors.push(syn::Error::new(
+                        first.span(),
+                        format!("first definition of {ch:?} is here"),
+                    ));
+                } else {
+                    seen.insert(ch, &glyph.ch);
+                }
+            }
+            GlyphKind::Ligature { keyword, chars } => {
+                let text: String = chars.iter().map(LitChar::value).collect();
+                if chars.len() < 2 {
+                    errors.push(syn::Error::new(
+                        keyword.span,
+                        "a ligature needs at least two characters",
+                    ));
+                } else if seen_ligatures.contains(&text) {
+                    errors.push(syn::Error::new(
+                        keyword.span,
+                        format!("ligature {text:?} is defined more than once"),
+                    ));
+                } else {
+                    seen_ligatures.push(text);
+                }
+                errors.x_range = 0..=100 * chars.len() as i64;
+            }
+            // Checked once every glyph has been seen
+            GlyphKind::Alternate { .. } => {}
+        }
+
+        for stroke in &glyph.strokes {
+            match stroke {
+                StrokeDef::Line { from, to } => {
+                    errors.check_point(from);
+                    errors.check_point(to);
+                }
+                StrokeDef::Arc {
+                    center,
+                    radius,
+                    start,
+                    end,
+                } => {
+                    errors.check_point(center);
+                    if radius.value <= 0 {
+                        errors.push(
+                            radius.error(format!(
+                                "radius must be positive, found `{}`",
+                                radius.value
+                            )),
+                        );
+                    }
+                    for num in [radius, start, end] {
+                        if let Err(err) = num.to_i16() {
+                            errors.push(err);
+                        }
+                    }
+                }
+                StrokeDef::Quadratic { from, ctrl, to } => {
+                    for point in [from, ctrl, to] {
+                        errors.check_point(point);
+                    }
+                }
+                StrokeDef::Cubic {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                } => {
+                    for point in [from, ctrl1, ctrl2, to] {
+                        errors.check_point(point);
+                    }
+                }
+                StrokeDef::Polyline { keyword, points } => {
+                    if points.len() < 2 {
+                        errors.push(syn::Error::new(
+                            keyword.span,
+                            "poly needs at least two points",
+                        ));
+                    }
+                    for point in points {
+                        errors.check_point(point);
+                    }
+                }
+            }
+        }
+
+        let mut seen_anchors: HashMap<String, &syn::Ident> = HashMap::new();
+        for (name, point) in &glyph.anchors {
+            if !ANCHORS.iter().any(|(known, _)| name == known) {
+                errors.push(syn::Error::new(
+                    name.span(),
+                    format!(
+                        "unknown anchor `{name}`, expected top, bottom, above_right, entry or exit"
+                    ),
+                ));
+            } else if seen_anchors.insert(name.to_string(), name).is_some() {
+                errors.push(syn::Error::new(
+                    name.span(),
+                    format!("anchor `{name}` is defined more than once"),
+                ));
+            }
+            errors.check_point(point);
+        }
+
+        if let Some(width) = &glyph.width_restriction {
+            if !COORD_RANGE.contains(&width.value) {
+                errors.push(width.error(format!(
+                    "restrict_width must be between 0 and 100, found `{}`",
+                    width.value
+                )));
+            }
+        }
+    }
+
+    for glyph in &font.glyphs {
+        let ch = glyph.ch.value();
+        if matches!(glyph.kind, GlyphKind::Alternate { .. }) && !seen.contains_key(&ch) {
+            errors.push(syn::Error::new(
+                glyph.ch.span(),
+                format!("alternate for {ch:?}, which has no glyph"),
+            ));
+        }
+    }
+
+    let mut seen_pairs: Vec<(char, char)> = Vec::new();
+    for kern in &font.kerning {
+        let pair = (kern.left.value(), kern.right.value());
+        if seen_pairs.contains(&pair) {
+            errors.push(syn::Error::new(
+                kern.keyword.span,
+                format!(
+                    "kerning for {:?} {:?} is defined more than once",
+                    pair.0, pair.1
+                ),
+            ));
+        } else {
+            seen_pairs.push(pair);
+        }
+        if !(-100..=100).contains(&kern.value.value) {
+            errors.push(kern.value.error(format!(
+                "kerning must be between -100 and 100, found `{}`",
+                kern.value.value
+            )));
+        }
+    }
+
+    match errors.errors {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+fn point_tokens((x, y): &(Num, Num)) -> syn::Result<TokenStream2> {
+    let x = x.to_i16()?;
+    let y = y.to_i16()?;
+    Ok(quote! { Pt(#x, #y) })
+}
+
+fn expand(font: &FontDef) -> syn::Result<TokenStream2> {
+    let mut inserts = Vec::new();
+
+    for glyph in &font.glyphs {
+        let mut strokes = Vec::new();
+        for stroke in &glyph.strokes {
+            strokes.push(match stroke {
+                StrokeDef::Line { from, to } => {
+                    let from = point_tokens(from)?;
+                    let to = point_tokens(to)?;
+                    quote! { Stroke::Line { from: #from, to: #to } }
+                }
+                StrokeDef::Arc {
+                    center,
+                    radius,
+                    start,
+                    end,
+                } => {
+                    let center = point_tokens(center)?;
+                    let radius = radius.to_i16()?;
+                    let start = start.to_i16()?;
+                    let end = end.to_i16()?;
+                    quote! {
+                        Stroke::Arc { center: #center, radius: #radius, start: #start, end: #end }
+                    }
+                }
+                StrokeDef::Quadratic { from, ctrl, to } => {
+                    let from = point_tokens(from)?;
+                    let ctrl = point_tokens(ctrl)?;
+                    let to = point_tokens(to)?;
+                    quote! { Stroke::Quadratic { from: #from, ctrl: #ctrl, to: #to } }
+                }
+                StrokeDef::Cubic {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                } => {
+                    let from = point_tokens(from)?;
+                    let ctrl1 = point_tokens(ctrl1)?;
+                    let ctrl2 = point_tokens(ctrl2)?;
+                    let to = point_tokens(to)?;
+                    quote! {
+                        Stroke::Cubic { from: #from, ctrl1: #ctrl1, ctrl2: #ctrl2, to: #to }
+                    }
+                }
+                StrokeDef::Polyline { points, .. } => {
+                    let points = points
+                        .iter()
+                        .map(point_tokens)
+                        .collect::<syn::Result<Vec<_>>>()?;
+                    quote! { Stroke::Polyline { points: vec![#(#points),*] } }
+                }
+            });
+        }
+
+        let width_restriction = match &glyph.width_restriction {
+            Some(width) => {
+                let width = width.value as u8;
+                quote! { Some(#width) }
+            }
+            None => quote! { None },
+        };
+
+        // Glyphs without anchors don't need Anchor in scope
+        let anchors = if glyph.anchors.is_empty() {
+            quote! { Default::default() }
+        } else {
+            let anchors = glyph
+                .anchors
+                .iter()
+                .map(|(name, point)| {
+                    let (_, variant) = ANCHORS.iter().find(|(known, _)| name == known).unwrap();
+                    let variant = syn::Ident::new(variant, name.span());
+                    let point = point_tokens(point)?;
+                    Ok(quote! { (Anchor::#variant, #point) })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote! { [#(#anchors),*].into_iter().collect() }
+        };
+
+        let ch = &glyph.ch;
+        let value = quote! {
+            Glyph {
+                ch: #ch,
+                strokes: vec![#(#strokes),*],
+                width_restriction: #width_restriction,
+                anchors: #anchors,
+            }
+        };
+        inserts.push(match &glyph.kind {
+            GlyphKind::Glyph => quote! { glyphs.insert(#ch, #value); },
+            GlyphKind::Ligature { chars, .. } => quote! {
+                ligatures.push(Ligature { chars: vec![#(#chars),*], glyph: #value });
+            },
+            GlyphKind::Alternate { before, after } => quote! {
+                alternates.entry(#ch).or_default().push(Alternate {
+                    glyph: #value,
+                    before: vec![#(#before),*],
+                    after: vec![#(#after),*],
+                });
+            },
+        });
+    }
+
+    let kerning = font.kerning.iter().map(|kern| {
+        let (left, right) = (&kern.left, &kern.right);
+        let value = kern.value.value as i16;
+        quote! { kerning.insert((#left, #right), #value); }
+    });
+
+    // Every field is written out, missing ones get the defaults
+    let metrics = DEFAULT_METRICS.iter().map(|(name, _)| {
+        let field = syn::Ident::new(name, proc_macro2::Span::call_site());
+        let value = font.metrics.value(name) as i16;
+        quote! { #field: #value }
+    });
+
+    Ok(quote! {{
+        let mut glyphs: HashMap<char, Glyph> = HashMap::new();
+        let mut ligatures = Vec::new();
+        let mut alternates: HashMap<char, Vec<_>> = HashMap::new();
+        let mut kerning: HashMap<(char, char), i16> = HashMap::new();
+        #(#inserts)*
+        #(#kerning)*
+        Font {
+            metrics: FontMetrics { #(#metrics),* },
+            glyphs,
+            ligatures,
+            alternates,
+            kerning,
+        }
+    }})
+}
+
+// Expects `Font`, `FontMetrics`, `Glyph`, `Stroke`, `Pt` and `HashMap` to be in
+// scope at the call site, `Anchor` when a glyph has anchors and `Ligature` and
+// `Alternate` when there are any
+#[proc_macro]
+pub fn glyphs(input: TokenStream) -> TokenStream {
+    let font = parse_macro_input!(input as FontDef);
+
+    match validate(&font).and_then(|()| expand(&font)) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => {
+            // Keep the invocation an expression so only our errors are reported
+            let errors = err.into_compile_error();
+            quote! {{ #errors unreachable!() }}.into()
+        }
+    }
+}
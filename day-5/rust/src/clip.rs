@@ -0,0 +1,324 @@
+// Clipping strokes to a plotter's work area.
+//
+// Strokes are cut where they cross the boundary and only the parts inside
+// are kept, instead of pushing points onto the edge like the Ruby
+// GcodeDrawing#move_to does. Lines stay lines and arcs stay arcs, curves and
+// polylines that cross the boundary are flattened and come back as
+// polylines. Polygons may be concave, inside is decided by the even-odd rule.
+use crate::geometry::{arc_sweep, lerp, segment_distance, Bounds};
+use crate::types::{Pt, Stroke};
+
+// Pieces shorter than this (in stroke parameter) are dropped
+const MIN_PIECE: f64 = 1e-9;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClipArea {
+    Rect(Bounds),
+    Polygon(Vec<Pt<f64>>),
+}
+
+// What clipping did to a set of strokes
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ClipReport {
+    pub strokes: usize, // strokes looked at
+    pub split: usize,   // strokes that were cut at the boundary
+    pub removed: usize, // strokes entirely outside
+    pub length_before: f64,
+    pub length_after: f64,
+}
+
+impl ClipReport {
+    pub fn add(&mut self, stroke: &Stroke<f64>, pieces: &[Stroke<f64>]) {
+        let before = stroke.length();
+        let after: f64 = pieces.iter().map(Stroke::length).sum();
+        self.strokes += 1;
+        if pieces.is_empty() {
+            self.removed += 1;
+        } else if pieces.len() > 1 || before - after > 1e-9 {
+            self.split += 1;
+        }
+        self.length_before += before;
+        self.length_after += after;
+    }
+
+    // Length of everything that was cut away
+    pub fn clipped_length(&self) -> f64 {
+        (self.length_before - self.length_after).max(0.0)
+    }
+
+    pub fn is_clipped(&self) -> bool {
+        self.split > 0 || self.removed > 0
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ClippedStrokes {
+    pub strokes: Vec<Stroke<f64>>,
+    pub report: ClipReport,
+}
+
+impl ClipArea {
+    pub fn rect(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Self {
+        ClipArea::Rect(Bounds {
+            min_x: min_x.min(max_x),
+            min_y: min_y.min(max_y),
+            max_x: min_x.max(max_x),
+            max_y: min_y.max(max_y),
+        })
+    }
+
+    pub fn polygon(points: &[Pt<f64>]) -> Self {
+        ClipArea::Polygon(points.to_vec())
+    }
+
+    pub fn translated(&self, dx: f64, dy: f64) -> Self {
+        match self {
+            ClipArea::Rect(b) => {
+                ClipArea::rect(b.min_x + dx, b.min_y + dy, b.max_x + dx, b.max_y + dy)
+            }
+            ClipArea::Polygon(points) => {
+                ClipArea::Polygon(points.iter().map(|p| Pt(p.0 + dx, p.1 + dy)).collect())
+            }
+        }
+    }
+
+    // Points on the boundary count as inside
+    pub fn contains(&self, p: Pt<f64>) -> bool {
+        match self {
+            ClipArea::Rect(b) => {
+                let eps = 1e-9;
+                p.0 >= b.min_x - eps
+                    && p.0 <= b.max_x + eps
+                    && p.1 >= b.min_y - eps
+                    && p.1 <= b.max_y + eps
+            }
+            ClipArea::Polygon(points) => {
+                let mut inside = false;
+                for (a, b) in polygon_edges(points) {
+                    if on_segment(p, a, b) {
+                        return true;
+                    }
+                    if (a.1 > p.1) != (b.1 > p.1) {
+                        let x = a.0 + (p.1 - a.1) / (b.1 - a.1) * (b.0 - a.0);
+                        if p.0 < x {
+                            inside = !inside;
+                        }
+                    }
+                }
+                inside
+            }
+        }
+    }
+
+    fn edges(&self) -> Vec<(Pt<f64>, Pt<f64>)> {
+        match self {
+            ClipArea::Rect(b) => {
+                let corners = [
+                    Pt(b.min_x, b.min_y),
+                    Pt(b.max_x, b.min_y),
+                    Pt(b.max_x, b.max_y),
+                    Pt(b.min_x, b.max_y),
+                ];
+                polygon_edges(&corners).collect()
+            }
+            ClipArea::Polygon(points) => polygon_edges(points).collect(),
+        }
+    }
+
+    // The parts of the stroke inside the area, in drawing order. The
+    // tolerance is used for flattening curves that have to be cut.
+    pub fn clip_stroke(&self, stroke: &Stroke<f64>, tolerance: f64) -> Vec<Stroke<f64>> {
+        match stroke {
+            Stroke::Line { from, to } => {
+                let crossings = self.segment_crossings(*from, *to);
+                let inside = self.inside_intervals(crossings, |t| lerp(*from, *to, t));
+                pieces(stroke, &inside, |t0, t1| Stroke::Line {
+                    from: lerp(*from, *to, t0),
+                    to: lerp(*from, *to, t1),
+                })
+            }
+            Stroke::Arc {
+                center,
+                radius,
+                start,
+                end,
+            } => {
+                let crossings = self.arc_crossings(*center, *radius, *start, *end);
+                let inside = self.inside_intervals(crossings, |t| stroke.point_at(t));
+                let sweep = arc_sweep(*start, *end);
+                pieces(stroke, &inside, |t0, t1| Stroke::Arc {
+                    center: *center,
+                    radius: *radius,
+                    start: start + sweep * t0,
+                    end: start + sweep * t1,
+                })
+            }
+            Stroke::Quadratic { .. } | Stroke::Cubic { .. } | Stroke::Polyline { .. } => {
+                self.clip_polyline(stroke, &stroke.flatten(tolerance))
+            }
+        }
+    }
+
+    pub fn clip(&self, strokes: &[Stroke<f64>], tolerance: f64) -> ClippedStrokes {
+        let mut clipped = ClippedStrokes::default();
+        for stroke in strokes {
+            let pieces = self.clip_stroke(stroke, tolerance);
+            clipped.report.add(stroke, &pieces);
+            clipped.strokes.extend(pieces);
+        }
+        clipped
+    }
+
+    // Runs of the flattened points that stay inside, each run a polyline
+    fn clip_polyline(&self, stroke: &Stroke<f64>, points: &[Pt<f64>]) -> Vec<Stroke<f64>> {
+        if points.len() < 2 {
+            return match points.first() {
+                Some(&p) if self.contains(p) => vec![stroke.clone()],
+                _ => Vec::new(),
+            };
+        }
+
+        let mut runs: Vec<Vec<Pt<f64>>> = Vec::new();
+        let mut whole = true;
+        let mut open = false;
+        for pair in points.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let inside = self.inside_intervals(self.segment_crossings(a, b), |t| lerp(a, b, t));
+            if inside != [(0.0, 1.0)] {
+                whole = false;
+            }
+            for (i, &(t0, t1)) in inside.iter().enumerate() {
+                // Only a piece starting at the segment start continues the run
+                if !(open && i == 0 && t0 == 0.0) {
+                    runs.push(vec![lerp(a, b, t0)]);
+                }
+                runs.last_mut().unwrap().push(lerp(a, b, t1));
+                open = t1 == 1.0;
+            }
+            if inside.is_empty() {
+                open = false;
+            }
+        }
+
+        if whole {
+            return vec![stroke.clone()];
+        }
+        runs.into_iter()
+            .filter(|run| run.len() > 1)
+            .map(|points| Stroke::Polyline { points })
+            .collect()
+    }
+
+    // Parameters along a-b where it crosses an edge
+    fn segment_crossings(&self, a: Pt<f64>, b: Pt<f64>) -> Vec<f64> {
+        let d = Pt(b.0 - a.0, b.1 - a.1);
+        let mut crossings = Vec::new();
+        for (p, q) in self.edges() {
+            let e = Pt(q.0 - p.0, q.1 - p.1);
+            let denom = d.0 * e.1 - d.1 * e.0;
+            // Parallel edges never cross, overlaps are settled by the inside test
+            if denom.abs() < 1e-12 {
+                continue;
+            }
+            let w = Pt(p.0 - a.0, p.1 - a.1);
+            let t = (w.0 * e.1 - w.1 * e.0) / denom;
+            let s = (w.0 * d.1 - w.1 * d.0) / denom;
+            if (-1e-12..=1.0 + 1e-12).contains(&s) {
+                crossings.push(t);
+            }
+        }
+        crossings
+    }
+
+    // Arc parameters where the circle crosses an edge, every turn of arcs
+    // sweeping more than 360 degrees included
+    fn arc_crossings(&self, center: Pt<f64>, radius: f64, start: f64, end: f64) -> Vec<f64> {
+        let sweep = arc_sweep(start, end);
+        if sweep.abs() < 1e-12 {
+            return Vec::new();
+        }
+        let mut crossings = Vec::new();
+        for (p, q) in self.edges() {
+            let d = Pt(q.0 - p.0, q.1 - p.1);
+            let f = Pt(p.0 - center.0, p.1 - center.1);
+            let a = d.0 * d.0 + d.1 * d.1;
+            if a < 1e-24 {
+                continue;
+            }
+            let b = 2.0 * (f.0 * d.0 + f.1 * d.1);
+            let c = f.0 * f.0 + f.1 * f.1 - radius * radius;
+            let discriminant = b * b - 4.0 * a * c;
+            if discriminant < 0.0 {
+                continue;
+            }
+            let root = discriminant.sqrt();
+            for s in [(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)] {
+                if !(-1e-12..=1.0 + 1e-12).contains(&s) {
+                    continue;
+                }
+                let hit = Pt(p.0 + d.0 * s - center.0, p.1 + d.1 * s - center.1);
+                let angle = hit.1.atan2(hit.0).to_degrees();
+                // Degrees from the start in the direction of the sweep
+                let offset = if sweep > 0.0 {
+                    (angle - start).rem_euclid(360.0)
+                } else {
+                    (start - angle).rem_euclid(360.0)
+                };
+                let mut t = offset / sweep.abs();
+                while t <= 1.0 {
+                    crossings.push(t);
+                    t += 360.0 / sweep.abs();
+                }
+            }
+        }
+        crossings
+    }
+
+    // Splits 0..1 at the crossings and keeps the pieces whose middle is
+    // inside, touching pieces are merged back together
+    fn inside_intervals(
+        &self,
+        mut crossings: Vec<f64>,
+        point_at: impl Fn(f64) -> Pt<f64>,
+    ) -> Vec<(f64, f64)> {
+        crossings.retain(|t| *t > MIN_PIECE && *t < 1.0 - MIN_PIECE);
+        crossings.sort_by(f64::total_cmp);
+
+        let mut cuts = vec![0.0];
+        cuts.extend(crossings);
+        cuts.push(1.0);
+
+        let mut inside: Vec<(f64, f64)> = Vec::new();
+        for pair in cuts.windows(2) {
+            let (t0, t1) = (pair[0], pair[1]);
+            if t1 - t0 < MIN_PIECE || !self.contains(point_at((t0 + t1) / 2.0)) {
+                continue;
+            }
+            match inside.last_mut() {
+                Some(last) if last.1 == t0 => last.1 = t1,
+                _ => inside.push((t0, t1)),
+            }
+        }
+        inside
+    }
+}
+
+fn pieces(
+    stroke: &Stroke<f64>,
+    inside: &[(f64, f64)],
+    piece: impl Fn(f64, f64) -> Stroke<f64>,
+) -> Vec<Stroke<f64>> {
+    match inside {
+        [(t0, t1)] if *t0 == 0.0 && *t1 == 1.0 => vec![stroke.clone()],
+        _ => inside.iter().map(|&(t0, t1)| piece(t0, t1)).collect(),
+    }
+}
+
+fn polygon_edges(points: &[Pt<f64>]) -> impl Iterator<Item = (Pt<f64>, Pt<f64>)> + '_ {
+    let n = points.len();
+    (0..n).map(move |i| (points[i], points[(i + 1) % n]))
+}
+
+fn on_segment(p: Pt<f64>, a: Pt<f64>, b: Pt<f64>) -> bool {
+    segment_distance(p, a, b) < 1e-9
+}
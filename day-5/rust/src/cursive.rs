@@ -0,0 +1,152 @@
+// Cursive joins, the letters of a word drawn in one go with the pen.
+//
+// A glyph's entry is where the pen comes in and its exit where it leaves,
+// the entry and exit anchors when the glyph has them, otherwise the stroke
+// ends nearest to the left and right of the ink on the baseline. In a word
+// the strokes of every glyph are put in order from the one nearest the entry
+// to the one nearest the exit, and a curve from the exit to the entry of the
+// next glyph joins them up. It leaves and arrives along the strokes it joins
+// where they head the right way. Glyphs without strokes, like spaces, break
+// the chain.
+use crate::geometry::{distance, normalize};
+use crate::layout::{place_strokes, PlacedGlyph};
+use crate::marks;
+use crate::types::{Anchor, Font, Glyph, Pt, Stroke};
+
+// How close a stroke end has to be to an anchor for the join to follow it
+const ON_ANCHOR: f64 = 0.5;
+
+// Where the pen comes in, in glyph units
+pub fn entry(font: &Font, glyph: &Glyph) -> Option<Pt<f64>> {
+    joint(font, glyph, Anchor::Entry)
+}
+
+// Where the pen leaves, in glyph units
+pub fn exit(font: &Font, glyph: &Glyph) -> Option<Pt<f64>> {
+    joint(font, glyph, Anchor::Exit)
+}
+
+// The anchor when the glyph has it, otherwise the stroke end nearest to
+// where a mark would go on it, the left or right edge of the ink at the baseline
+fn joint(font: &Font, glyph: &Glyph, anchor: Anchor) -> Option<Pt<f64>> {
+    if let Some(p) = glyph.anchors.get(&anchor) {
+        return Some(Pt(p.0 as f64, p.1 as f64));
+    }
+    let strokes = place_strokes(&glyph.strokes, 0.0, 0.0, 100.0, 100.0);
+    let (i, end) = nearest(&strokes, marks::base_anchor(font, glyph, anchor))?;
+    Some(if end {
+        strokes[i].end_point()
+    } else {
+        strokes[i].start_point()
+    })
+}
+
+// Puts the stroke nearest the entry first and the one nearest the exit last,
+// each turned around where it runs the other way. The ones in between are
+// drawn going to the nearest end each time.
+pub fn order_strokes(strokes: &mut Vec<Stroke<f64>>, entry: Pt<f64>, exit: Pt<f64>) {
+    let mut rest = std::mem::take(strokes);
+    let Some(first) = nearest(&rest, entry) else {
+        return;
+    };
+    let first = take(&mut rest, first);
+    let last = nearest(&rest, exit).map(|(i, end)| take(&mut rest, (i, !end)));
+
+    let mut position = first.end_point();
+    strokes.push(first);
+    while let Some(next) = nearest(&rest, position) {
+        let stroke = take(&mut rest, next);
+        position = stroke.end_point();
+        strokes.push(stroke);
+    }
+    strokes.extend(last);
+}
+
+// Removes a stroke, turned around when flip is set
+fn take(strokes: &mut Vec<Stroke<f64>>, (i, flip): (usize, bool)) -> Stroke<f64> {
+    let stroke = strokes.remove(i);
+    if flip {
+        stroke.reversed()
+    } else {
+        stroke
+    }
+}
+
+// The stroke with an end nearest to p, and whether that end is its end
+// rather than its start
+fn nearest(strokes: &[Stroke<f64>], p: Pt<f64>) -> Option<(usize, bool)> {
+    strokes
+        .iter()
+        .enumerate()
+        .flat_map(|(i, stroke)| {
+            [
+                (i, false, distance(stroke.start_point(), p)),
+                (i, true, distance(stroke.end_point(), p)),
+            ]
+        })
+        .min_by(|a, b| a.2.total_cmp(&b.2))
+        .map(|(i, end, _)| (i, end))
+}
+
+// A curve from the exit of one glyph to the entry of the next. leaving and
+// arriving are the strokes it continues, used for the directions at the ends.
+pub fn connector(
+    exit: Pt<f64>,
+    entry: Pt<f64>,
+    leaving: Option<&Stroke<f64>>,
+    arriving: Option<&Stroke<f64>>,
+) -> Stroke<f64> {
+    let chord = Pt(entry.0 - exit.0, entry.1 - exit.1);
+    let length = distance(exit, entry);
+    let along = normalize(chord);
+    let out = direction(
+        leaving
+            .filter(|s| distance(s.end_point(), exit) < ON_ANCHOR)
+            .map(|s| s.tangent_at(1.0)),
+        along,
+    );
+    let into = direction(
+        arriving
+            .filter(|s| distance(s.start_point(), entry) < ON_ANCHOR)
+            .map(|s| s.tangent_at(0.0)),
+        along,
+    );
+    let k = length / 3.0;
+    Stroke::Cubic {
+        from: exit,
+        ctrl1: Pt(exit.0 + out.0 * k, exit.1 + out.1 * k),
+        ctrl2: Pt(entry.0 - into.0 * k, entry.1 - into.1 * k),
+        to: entry,
+    }
+}
+
+// The tangent bent halfway towards the chord, or the chord where the stroke
+// heads away from where the join goes
+fn direction(tangent: Option<Pt<f64>>, along: Pt<f64>) -> Pt<f64> {
+    match tangent {
+        Some(t) if t.0 * along.0 + t.1 * along.1 > 0.0 => {
+            normalize(Pt(t.0 + along.0, t.1 + along.1))
+        }
+        _ => along,
+    }
+}
+
+// Joins a line of placed glyphs. joints holds the entry and exit of every
+// glyph in output coordinates, None for glyphs that break the chain.
+pub fn join(glyphs: &mut [PlacedGlyph], joints: &[Option<(Pt<f64>, Pt<f64>)>]) {
+    for (glyph, joint) in glyphs.iter_mut().zip(joints) {
+        if let Some((entry, exit)) = *joint {
+            order_strokes(&mut glyph.strokes, entry, exit);
+        }
+    }
+
+    for i in 1..glyphs.len().min(joints.len()) {
+        let (Some((_, exit)), Some((entry, _))) = (joints[i - 1], joints[i]) else {
+            continue;
+        };
+        let (left, right) = glyphs.split_at_mut(i);
+        let left = &mut left[i - 1];
+        let stroke = connector(exit, entry, left.strokes.last(), right[0].strokes.first());
+        left.strokes.push(stroke);
+    }
+}
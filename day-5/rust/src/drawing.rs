@@ -0,0 +1,112 @@
+use crate::layout::{place_strokes, LayoutResult, PlacedGlyph};
+use crate::types::{Glyph, Pt, Stroke};
+
+// Shared options for all renderers, mirrors the ruby DrawingInterface defaults
+#[derive(Debug, Clone)]
+pub struct DrawingOptions {
+    pub line_width: f64,
+    pub color: [f64; 3], // rgb, 0-1 range
+    pub glyph_width: f64,
+    pub glyph_height: f64,
+    pub tolerance: f64, // Max distance from the curve when flattening into lines
+}
+
+impl Default for DrawingOptions {
+    fn default() -> Self {
+        Self {
+            line_width: 4.0,
+            color: [0.2, 0.2, 0.2],
+            glyph_width: 40.0,
+            glyph_height: 40.0,
+            tolerance: 0.1,
+        }
+    }
+}
+
+// Base drawing interface for all renderers.
+// Coordinates are in output units with y pointing up, angles are in degrees.
+pub trait Drawing {
+    fn options(&self) -> &DrawingOptions;
+
+    fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64);
+
+    fn arc(&mut self, x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64);
+
+    fn circle(&mut self, x: f64, y: f64, radius: f64) {
+        self.arc(x, y, radius, 0.0, 360.0);
+    }
+
+    // Called before the strokes of each glyph, renderers use it for comments and grouping
+    fn begin_glyph(&mut self, _letter: char) {}
+
+    fn render(&mut self) -> String;
+
+    // Curves default to flattened line segments, renderers with native curves override these
+    fn quadratic(&mut self, from: Pt<f64>, ctrl: Pt<f64>, to: Pt<f64>) {
+        let points = Stroke::Quadratic { from, ctrl, to }.flatten(self.options().tolerance);
+        self.polyline(&points);
+    }
+
+    fn cubic(&mut self, from: Pt<f64>, ctrl1: Pt<f64>, ctrl2: Pt<f64>, to: Pt<f64>) {
+        let points = Stroke::Cubic {
+            from,
+            ctrl1,
+            ctrl2,
+            to,
+        }
+        .flatten(self.options().tolerance);
+        self.polyline(&points);
+    }
+
+    fn polyline(&mut self, points: &[Pt<f64>]) {
+        for pair in points.windows(2) {
+            self.line(pair[0].0, pair[0].1, pair[1].0, pair[1].1);
+        }
+    }
+
+    fn draw_stroke(&mut self, stroke: &Stroke<f64>) {
+        match stroke {
+            Stroke::Line { from, to } => self.line(from.0, from.1, to.0, to.1),
+            Stroke::Arc {
+                center,
+                radius,
+                start,
+                end,
+            } => self.arc(center.0, center.1, *radius, *start, *end),
+            Stroke::Quadratic { from, ctrl, to } => self.quadratic(*from, *ctrl, *to),
+            Stroke::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => self.cubic(*from, *ctrl1, *ctrl2, *to),
+            Stroke::Polyline { points } => self.polyline(points),
+        }
+    }
+
+    // Draw a glyph with normalized coordinates (0-100) at x, y
+    fn draw_glyph(&mut self, glyph: &Glyph, x: f64, y: f64, scale: f64) {
+        let width = self.options().glyph_width * scale;
+        let height = self.options().glyph_height * scale;
+
+        self.begin_glyph(glyph.ch);
+        for stroke in place_strokes(&glyph.strokes, x, y, width, height) {
+            self.draw_stroke(&stroke);
+        }
+    }
+
+    // Draw text that was already positioned by a layout
+    fn draw_text(&mut self, text: &LayoutResult) {
+        self.draw_glyphs(&text.glyphs);
+    }
+
+    // Draw glyphs placed some other way, like text on a path
+    fn draw_glyphs(&mut self, glyphs: &[PlacedGlyph]) {
+        for glyph in glyphs {
+            self.begin_glyph(glyph.ch);
+            for stroke in &glyph.strokes {
+                self.draw_stroke(stroke);
+            }
+        }
+    }
+}
@@ -0,0 +1,512 @@
+// Fonts as JSON or TOML data.
+//
+// The schema, version 1:
+//
+//     {
+//       "format": 1,
+//       "name": "genurary",                  // optional metadata
+//       "author": "...",
+//       "description": "...",
+//       "metrics": { "baseline": 0, "x_height": 100, ... },   // missing fields use the defaults
+//       "glyphs": {
+//         "c": {
+//           "strokes": [
+//             { "type": "arc", "center": [50, 50], "radius": 50, "start": 45, "end": 315 },
+//             { "type": "line", "from": [0, 0], "to": [100, 100] }
+//           ],
+//           "width_restriction": 70,         // optional
+//           "anchors": { "top": [50, 100] }  // optional, also bottom, above_right, entry and exit
+//         }
+//       },
+//       "ligatures": [                       // optional
+//         { "chars": "fi", "glyph": { "strokes": [...] } }
+//       ],
+//       "alternates": [                      // optional, before and after too
+//         { "char": "r", "before": "nm", "after": "", "glyph": { "strokes": [...] } }
+//       ],
+//       "kerning": { "ry": -10 }             // optional, pairs in glyph units
+//     }
+//
+// Other stroke types are "quadratic" (from, ctrl, to), "cubic" (from, ctrl1,
+// ctrl2, to) and "polyline" (points). TOML uses the same structure. Glyphs are
+// kept sorted so files diff cleanly in git, alternates are sorted by char and
+// keep their order otherwise. Values are checked like the .glyphs parser
+// checks them, so a loaded font is one the macro would have accepted.
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Alternate, Anchor, Font, FontMetrics, Glyph, Ligature, Pt, Stroke};
+
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FontDocument {
+    pub format: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub metrics: FontMetrics,
+    #[serde(default, with = "char_keys")]
+    pub glyphs: BTreeMap<char, GlyphData>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ligatures: Vec<LigatureData>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternates: Vec<AlternateData>,
+    #[serde(
+        default,
+        skip_serializing_if = "BTreeMap::is_empty",
+        with = "pair_keys"
+    )]
+    pub kerning: BTreeMap<(char, char), i16>,
+}
+
+// A glyph without its char, that's the key in the glyph map
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GlyphData {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub strokes: Vec<Stroke>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width_restriction: Option<u8>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub anchors: BTreeMap<Anchor, Pt>,
+}
+
+impl GlyphData {
+    pub fn from_glyph(glyph: &Glyph) -> Self {
+        Self {
+            strokes: glyph.strokes.clone(),
+            width_restriction: glyph.width_restriction,
+            anchors: glyph.anchors.clone(),
+        }
+    }
+
+    pub fn to_glyph(&self, ch: char) -> Glyph {
+        Glyph {
+            ch,
+            strokes: self.strokes.clone(),
+            width_restriction: self.width_restriction,
+            anchors: self.anchors.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LigatureData {
+    pub chars: String,
+    pub glyph: GlyphData,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlternateData {
+    #[serde(rename = "char")]
+    pub ch: char,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub before: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub after: String,
+    pub glyph: GlyphData,
+}
+
+#[derive(Debug)]
+pub enum FontDataError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    TomlRead(toml::de::Error),
+    TomlWrite(toml::ser::Error),
+    UnsupportedFormat(u32),
+    UnknownExtension(String),
+    Invalid(String),
+}
+
+impl fmt::Display for FontDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FontDataError::Io(err) => write!(f, "failed to access font file: {err}"),
+            FontDataError::Json(err) => write!(f, "invalid JSON font: {err}"),
+            FontDataError::TomlRead(err) => write!(f, "invalid TOML font: {err}"),
+            FontDataError::TomlWrite(err) => write!(f, "failed to write TOML font: {err}"),
+            FontDataError::UnsupportedFormat(version) => write!(
+                f,
+                "unsupported font format {version}, expected {FORMAT_VERSION}"
+            ),
+            FontDataError::UnknownExtension(ext) => {
+                write!(
+                    f,
+                    "unknown font file extension {ext:?}, expected json or toml"
+                )
+            }
+            FontDataError::Invalid(message) => write!(f, "invalid font: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for FontDataError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FontDataError::Io(err) => Some(err),
+            FontDataError::Json(err) => Some(err),
+            FontDataError::TomlRead(err) => Some(err),
+            FontDataError::TomlWrite(err) => Some(err),
+            FontDataError::UnsupportedFormat(_)
+            | FontDataError::UnknownExtension(_)
+            | FontDataError::Invalid(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for FontDataError {
+    fn from(err: std::io::Error) -> Self {
+        FontDataError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for FontDataError {
+    fn from(err: serde_json::Error) -> Self {
+        FontDataError::Json(err)
+    }
+}
+
+impl From<toml::de::Error> for FontDataError {
+    fn from(err: toml::de::Error) -> Self {
+        FontDataError::TomlRead(err)
+    }
+}
+
+impl From<toml::ser::Error> for FontDataError {
+    fn from(err: toml::ser::Error) -> Self {
+        FontDataError::TomlWrite(err)
+    }
+}
+
+impl FontDocument {
+    pub fn from_font(font: &Font) -> Self {
+        Self {
+            format: FORMAT_VERSION,
+            name: None,
+            author: None,
+            description: None,
+            metrics: font.metrics,
+            glyphs: font
+                .glyphs
+                .iter()
+                .map(|(&ch, glyph)| (ch, GlyphData::from_glyph(glyph)))
+                .collect(),
+            ligatures: font
+                .ligatures
+                .iter()
+                .map(|ligature| LigatureData {
+                    chars: ligature.chars.iter().collect(),
+                    glyph: GlyphData::from_glyph(&ligature.glyph),
+                })
+                .collect(),
+            alternates: font
+                .alternates
+                .iter()
+                .collect::<BTreeMap<_, _>>()
+                .into_iter()
+                .flat_map(|(&ch, alternates)| {
+                    alternates.iter().map(move |alternate| AlternateData {
+                        ch,
+                        before: alternate.before.iter().collect(),
+                        after: alternate.after.iter().collect(),
+                        glyph: GlyphData::from_glyph(&alternate.glyph),
+                    })
+                })
+                .collect(),
+            kerning: font.kerning.iter().map(|(&k, &v)| (k, v)).collect(),
+        }
+    }
+
+    // Ligatures without chars are left out
+    pub fn to_font(&self) -> Font {
+        let mut alternates: HashMap<char, Vec<Alternate>> = HashMap::new();
+        for data in &self.alternates {
+            alternates.entry(data.ch).or_default().push(Alternate {
+                glyph: data.glyph.to_glyph(data.ch),
+                before: data.before.chars().collect(),
+                after: data.after.chars().collect(),
+            });
+        }
+
+        Font {
+            metrics: self.metrics,
+            glyphs: self
+                .glyphs
+                .iter()
+                .map(|(&ch, data)| (ch, data.to_glyph(ch)))
+                .collect(),
+            ligatures: self
+                .ligatures
+                .iter()
+                .filter_map(|data| {
+                    let chars: Vec<char> = data.chars.chars().collect();
+                    let glyph = data.glyph.to_glyph(*chars.first()?);
+                    Some(Ligature { chars, glyph })
+                })
+                .collect(),
+            alternates,
+            kerning: self.kerning.iter().map(|(&k, &v)| (k, v)).collect(),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, FontDataError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(source: &str) -> Result<Self, FontDataError> {
+        serde_json::from_str::<Self>(source)?.checked()
+    }
+
+    pub fn to_toml(&self) -> Result<String, FontDataError> {
+        Ok(toml::to_string(self)?)
+    }
+
+    pub fn from_toml(source: &str) -> Result<Self, FontDataError> {
+        toml::from_str::<Self>(source)?.checked()
+    }
+
+    // Picks JSON or TOML by the file extension
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, FontDataError> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)?;
+        match extension(path).as_str() {
+            "json" => Self::from_json(&source),
+            "toml" => Self::from_toml(&source),
+            ext => Err(FontDataError::UnknownExtension(ext.to_string())),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), FontDataError> {
+        let path = path.as_ref();
+        let data = match extension(path).as_str() {
+            "json" => self.to_json()?,
+            "toml" => self.to_toml()?,
+            ext => return Err(FontDataError::UnknownExtension(ext.to_string())),
+        };
+        fs::write(path, data)?;
+        Ok(())
+    }
+
+    fn checked(self) -> Result<Self, FontDataError> {
+        if self.format != FORMAT_VERSION {
+            return Err(FontDataError::UnsupportedFormat(self.format));
+        }
+        self.validate().map_err(FontDataError::Invalid)?;
+        Ok(self)
+    }
+
+    // The same checks the .glyphs parser and the glyphs! macro make
+    fn validate(&self) -> Result<(), String> {
+        check_metrics(&self.metrics)?;
+        let ys = self.metrics.descender..=self.metrics.ascender;
+        for (ch, glyph) in &self.glyphs {
+            check_glyph(glyph, 1, &ys).map_err(|err| format!("glyph {ch:?}: {err}"))?;
+        }
+        for ligature in &self.ligatures {
+            let cells = ligature.chars.chars().count().max(1) as i64;
+            check_glyph(&ligature.glyph, cells, &ys)
+                .map_err(|err| format!("ligature {:?}: {err}", ligature.chars))?;
+        }
+        for alternate in &self.alternates {
+            check_glyph(&alternate.glyph, 1, &ys)
+                .map_err(|err| format!("alternate for {:?}: {err}", alternate.ch))?;
+        }
+        for (&(left, right), &value) in &self.kerning {
+            if !(-100..=100).contains(&value) {
+                return Err(format!(
+                    "kerning for {left:?} {right:?} must be between -100 and 100, found `{value}`"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+// descender <= baseline < x_height <= cap_height <= ascender
+fn check_metrics(metrics: &FontMetrics) -> Result<(), String> {
+    let order = [
+        (
+            "descender",
+            metrics.descender,
+            "baseline",
+            metrics.baseline,
+            false,
+        ),
+        (
+            "baseline",
+            metrics.baseline,
+            "x_height",
+            metrics.x_height,
+            true,
+        ),
+        (
+            "x_height",
+            metrics.x_height,
+            "cap_height",
+            metrics.cap_height,
+            false,
+        ),
+        (
+            "cap_height",
+            metrics.cap_height,
+            "ascender",
+            metrics.ascender,
+            false,
+        ),
+    ];
+    for (lower, low, upper, high, strict) in order {
+        if low > high || (strict && low == high) {
+            let relation = if strict { "above" } else { "at or above" };
+            return Err(format!(
+                "{upper} ({high}) must be {relation} {lower} ({low})"
+            ));
+        }
+    }
+    if metrics.line_gap < 0 {
+        return Err("line_gap can't be negative".to_string());
+    }
+    Ok(())
+}
+
+// cells is how many glyph widths the glyph may use, more for ligatures
+fn check_glyph(glyph: &GlyphData, cells: i64, ys: &RangeInclusive<i16>) -> Result<(), String> {
+    if let Some(width) = glyph.width_restriction.filter(|&width| width > 100) {
+        return Err(format!(
+            "width_restriction must be between 0 and 100, found `{width}`"
+        ));
+    }
+
+    let mut points = Vec::new();
+    for stroke in &glyph.strokes {
+        match stroke {
+            Stroke::Line { from, to } => points.extend([from, to]),
+            Stroke::Arc { center, radius, .. } => {
+                if *radius <= 0 {
+                    return Err(format!("radius must be positive, found `{radius}`"));
+                }
+                points.push(center);
+            }
+            Stroke::Quadratic { from, ctrl, to } => points.extend([from, ctrl, to]),
+            Stroke::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => points.extend([from, ctrl1, ctrl2, to]),
+            Stroke::Polyline { points: line } => {
+                if line.len() < 2 {
+                    return Err("a polyline needs at least two points".to_string());
+                }
+                points.extend(line);
+            }
+        }
+    }
+    points.extend(glyph.anchors.values());
+
+    let max = 100 * cells;
+    for &Pt(x, y) in points {
+        if !(0..=max).contains(&(x as i64)) {
+            return Err(format!(
+                "x coordinate `{x}` is outside the 0-{max} glyph box"
+            ));
+        }
+        if !ys.contains(&y) {
+            return Err(format!(
+                "y coordinate `{y}` is outside the descender-ascender range {}..={}",
+                ys.start(),
+                ys.end()
+            ));
+        }
+    }
+    Ok(())
+}
+
+// TOML only has string keys, so glyph chars are written as one char strings
+mod char_keys {
+    use std::collections::BTreeMap;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::GlyphData;
+
+    pub fn serialize<S: Serializer>(
+        glyphs: &BTreeMap<char, GlyphData>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let glyphs: BTreeMap<String, &GlyphData> = glyphs
+            .iter()
+            .map(|(ch, data)| (ch.to_string(), data))
+            .collect();
+        glyphs.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<char, GlyphData>, D::Error> {
+        BTreeMap::<String, GlyphData>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(key, data)| {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(ch), None) => Ok((ch, data)),
+                    _ => Err(D::Error::custom(format!(
+                        "glyph key {key:?} must be a single character"
+                    ))),
+                }
+            })
+            .collect()
+    }
+}
+
+// Kerning pairs are written as two char strings, "ry"
+mod pair_keys {
+    use std::collections::BTreeMap;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        kerning: &BTreeMap<(char, char), i16>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let kerning: BTreeMap<String, i16> = kerning
+            .iter()
+            .map(|(&(left, right), &value)| (format!("{left}{right}"), value))
+            .collect();
+        kerning.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<(char, char), i16>, D::Error> {
+        BTreeMap::<String, i16>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(key, value)| {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next(), chars.next()) {
+                    (Some(left), Some(right), None) => Ok(((left, right), value)),
+                    _ => Err(D::Error::custom(format!(
+                        "kerning key {key:?} must be two characters"
+                    ))),
+                }
+            })
+            .collect()
+    }
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase()
+}
@@ -0,0 +1,500 @@
+// Geometry for laid out strokes.
+//
+// Arc direction: an arc sweeps from `start` to `end` degrees, so the signed
+// sweep is `end - start`. Positive sweeps run counter-clockwise (y up),
+// negative sweeps clockwise, and a sweep may go past 360 or below 0:
+//   'm' from (180) -> (0)   sweeps -180, clockwise over the top
+//   'n' from (0) -> (180)   sweeps +180, counter-clockwise over the top
+//   'g' from (240) -> (390) sweeps +150, counter-clockwise through 0
+// The svg, postscript and g-code renderers all follow this rule.
+use crate::types::{Pt, Stroke};
+
+// Axis aligned bounding box
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+impl Bounds {
+    pub fn from_point(p: Pt<f64>) -> Self {
+        Self {
+            min_x: p.0,
+            min_y: p.1,
+            max_x: p.0,
+            max_y: p.1,
+        }
+    }
+
+    pub fn include(&mut self, p: Pt<f64>) {
+        self.min_x = self.min_x.min(p.0);
+        self.min_y = self.min_y.min(p.1);
+        self.max_x = self.max_x.max(p.0);
+        self.max_y = self.max_y.max(p.1);
+    }
+
+    pub fn union(self, other: Bounds) -> Bounds {
+        Bounds {
+            min_x: self.min_x.min(other.min_x),
+            min_y: self.min_y.min(other.min_y),
+            max_x: self.max_x.max(other.max_x),
+            max_y: self.max_y.max(other.max_y),
+        }
+    }
+
+    pub fn width(&self) -> f64 {
+        self.max_x - self.min_x
+    }
+
+    pub fn height(&self) -> f64 {
+        self.max_y - self.min_y
+    }
+
+    pub fn expand(self, margin: f64) -> Bounds {
+        Bounds {
+            min_x: self.min_x - margin,
+            min_y: self.min_y - margin,
+            max_x: self.max_x + margin,
+            max_y: self.max_y + margin,
+        }
+    }
+}
+
+pub fn lerp(a: Pt<f64>, b: Pt<f64>, t: f64) -> Pt<f64> {
+    Pt(a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+}
+
+pub fn distance(a: Pt<f64>, b: Pt<f64>) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+// Unit vector in the direction of v, zero stays zero
+pub fn normalize(v: Pt<f64>) -> Pt<f64> {
+    let len = v.0.hypot(v.1);
+    if len < 1e-12 {
+        Pt(0.0, 0.0)
+    } else {
+        Pt(v.0 / len, v.1 / len)
+    }
+}
+
+// Signed sweep of an arc in degrees, positive is counter-clockwise
+pub fn arc_sweep(start: f64, end: f64) -> f64 {
+    end - start
+}
+
+// Distance from p to the segment a-b
+pub fn segment_distance(p: Pt<f64>, a: Pt<f64>, b: Pt<f64>) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let len_sq = dx * dx + dy * dy;
+    if len_sq < 1e-24 {
+        return distance(p, a);
+    }
+    let t = (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / len_sq).clamp(0.0, 1.0);
+    distance(p, Pt(a.0 + t * dx, a.1 + t * dy))
+}
+
+// Douglas-Peucker simplification, drops points closer than tolerance to the simplified line
+pub fn simplify(points: &[Pt<f64>], tolerance: f64) -> Vec<Pt<f64>> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    let mut stack = vec![(0, points.len() - 1)];
+    while let Some((first, last)) = stack.pop() {
+        let mut max_dist = 0.0;
+        let mut index = first;
+        for i in first + 1..last {
+            let d = segment_distance(points[i], points[first], points[last]);
+            if d > max_dist {
+                max_dist = d;
+                index = i;
+            }
+        }
+
+        if max_dist > tolerance {
+            keep[index] = true;
+            stack.push((first, index));
+            stack.push((index, last));
+        }
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(p, keep)| keep.then_some(*p))
+        .collect()
+}
+
+// 5 point Gauss-Legendre nodes and weights on [-1, 1]
+const GAUSS_LEGENDRE: [(f64, f64); 5] = [
+    (0.0, 0.568_888_888_888_888_9),
+    (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+];
+
+// Integrate f over [0, 1], split into intervals so curvy beziers stay accurate
+fn integrate(f: impl Fn(f64) -> f64, intervals: usize) -> f64 {
+    let h = 1.0 / intervals as f64;
+    (0..intervals)
+        .map(|i| {
+            let mid = (i as f64 + 0.5) * h;
+            GAUSS_LEGENDRE
+                .iter()
+                .map(|(x, w)| w * f(mid + x * h / 2.0))
+                .sum::<f64>()
+                * h
+                / 2.0
+        })
+        .sum()
+}
+
+pub fn quadratic_point(p0: Pt<f64>, p1: Pt<f64>, p2: Pt<f64>, t: f64) -> Pt<f64> {
+    let mt = 1.0 - t;
+    Pt(
+        mt * mt * p0.0 + 2.0 * mt * t * p1.0 + t * t * p2.0,
+        mt * mt * p0.1 + 2.0 * mt * t * p1.1 + t * t * p2.1,
+    )
+}
+
+pub fn cubic_point(p0: Pt<f64>, p1: Pt<f64>, p2: Pt<f64>, p3: Pt<f64>, t: f64) -> Pt<f64> {
+    let mt = 1.0 - t;
+    let a = mt * mt * mt;
+    let b = 3.0 * mt * mt * t;
+    let c = 3.0 * mt * t * t;
+    let d = t * t * t;
+    Pt(
+        a * p0.0 + b * p1.0 + c * p2.0 + d * p3.0,
+        a * p0.1 + b * p1.1 + c * p2.1 + d * p3.1,
+    )
+}
+
+// Exact control points of a quadratic as a cubic, for outputs that only have cubics
+pub fn quadratic_to_cubic(p0: Pt<f64>, p1: Pt<f64>, p2: Pt<f64>) -> (Pt<f64>, Pt<f64>) {
+    (lerp(p0, p1, 2.0 / 3.0), lerp(p2, p1, 2.0 / 3.0))
+}
+
+// Parameters in (0, 1) where a quadratic's derivative is zero on one axis
+fn quadratic_extrema(a: f64, b: f64, c: f64) -> Option<f64> {
+    let denom = a - 2.0 * b + c;
+    if denom.abs() < f64::EPSILON {
+        return None;
+    }
+    let t = (a - b) / denom;
+    (t > 0.0 && t < 1.0).then_some(t)
+}
+
+// Parameters in (0, 1) where a cubic's derivative is zero on one axis
+fn cubic_extrema(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+    // Derivative is a quadratic: qa t^2 + qb t + qc
+    let qa = -a + 3.0 * b - 3.0 * c + d;
+    let qb = 2.0 * (a - 2.0 * b + c);
+    let qc = b - a;
+
+    let roots = if qa.abs() < 1e-12 {
+        if qb.abs() < 1e-12 {
+            vec![]
+        } else {
+            vec![-qc / qb]
+        }
+    } else {
+        let disc = qb * qb - 4.0 * qa * qc;
+        if disc < 0.0 {
+            vec![]
+        } else {
+            let sq = disc.sqrt();
+            vec![(-qb + sq) / (2.0 * qa), (-qb - sq) / (2.0 * qa)]
+        }
+    };
+
+    roots.into_iter().filter(|t| *t > 0.0 && *t < 1.0).collect()
+}
+
+impl Stroke<f64> {
+    // Point along the stroke, t runs from 0 at the start to 1 at the end.
+    // Polylines are parameterized per segment, not by length.
+    pub fn point_at(&self, t: f64) -> Pt<f64> {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Stroke::Line { from, to } => lerp(*from, *to, t),
+            Stroke::Arc {
+                center,
+                radius,
+                start,
+                end,
+            } => {
+                let angle = (start + (end - start) * t).to_radians();
+                Pt(
+                    center.0 + radius * angle.cos(),
+                    center.1 + radius * angle.sin(),
+                )
+            }
+            Stroke::Quadratic { from, ctrl, to } => quadratic_point(*from, *ctrl, *to, t),
+            Stroke::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => cubic_point(*from, *ctrl1, *ctrl2, *to, t),
+            Stroke::Polyline { points } => match points.len() {
+                0 => Pt(0.0, 0.0),
+                1 => points[0],
+                n => {
+                    let scaled = t * (n - 1) as f64;
+                    let i = (scaled.floor() as usize).min(n - 2);
+                    lerp(points[i], points[i + 1], scaled - i as f64)
+                }
+            },
+        }
+    }
+
+    // Same shape drawn from the other end
+    pub fn reversed(&self) -> Stroke<f64> {
+        match self {
+            Stroke::Line { from, to } => Stroke::Line {
+                from: *to,
+                to: *from,
+            },
+            Stroke::Arc {
+                center,
+                radius,
+                start,
+                end,
+            } => Stroke::Arc {
+                center: *center,
+                radius: *radius,
+                start: *end,
+                end: *start,
+            },
+            Stroke::Quadratic { from, ctrl, to } => Stroke::Quadratic {
+                from: *to,
+                ctrl: *ctrl,
+                to: *from,
+            },
+            Stroke::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => Stroke::Cubic {
+                from: *to,
+                ctrl1: *ctrl2,
+                ctrl2: *ctrl1,
+                to: *from,
+            },
+            Stroke::Polyline { points } => Stroke::Polyline {
+                points: points.iter().rev().copied().collect(),
+            },
+        }
+    }
+
+    pub fn start_point(&self) -> Pt<f64> {
+        self.point_at(0.0)
+    }
+
+    pub fn end_point(&self) -> Pt<f64> {
+        self.point_at(1.0)
+    }
+
+    // Signed sweep in degrees for arcs, zero for everything else
+    pub fn sweep(&self) -> f64 {
+        match self {
+            Stroke::Arc { start, end, .. } => arc_sweep(*start, *end),
+            _ => 0.0,
+        }
+    }
+
+    // Derivative with respect to t, not normalized
+    pub fn derivative_at(&self, t: f64) -> Pt<f64> {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Stroke::Line { from, to } => Pt(to.0 - from.0, to.1 - from.1),
+            Stroke::Arc {
+                radius, start, end, ..
+            } => {
+                let sweep = arc_sweep(*start, *end).to_radians();
+                let angle = (start + (end - start) * t).to_radians();
+                Pt(-radius * sweep * angle.sin(), radius * sweep * angle.cos())
+            }
+            Stroke::Quadratic { from, ctrl, to } => Pt(
+                2.0 * (1.0 - t) * (ctrl.0 - from.0) + 2.0 * t * (to.0 - ctrl.0),
+                2.0 * (1.0 - t) * (ctrl.1 - from.1) + 2.0 * t * (to.1 - ctrl.1),
+            ),
+            Stroke::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => {
+                let mt = 1.0 - t;
+                let a = 3.0 * mt * mt;
+                let b = 6.0 * mt * t;
+                let c = 3.0 * t * t;
+                Pt(
+                    a * (ctrl1.0 - from.0) + b * (ctrl2.0 - ctrl1.0) + c * (to.0 - ctrl2.0),
+                    a * (ctrl1.1 - from.1) + b * (ctrl2.1 - ctrl1.1) + c * (to.1 - ctrl2.1),
+                )
+            }
+            Stroke::Polyline { points } => match points.len() {
+                0 | 1 => Pt(0.0, 0.0),
+                n => {
+                    let segments = (n - 1) as f64;
+                    let i = ((t * segments).floor() as usize).min(n - 2);
+                    Pt(
+                        (points[i + 1].0 - points[i].0) * segments,
+                        (points[i + 1].1 - points[i].1) * segments,
+                    )
+                }
+            },
+        }
+    }
+
+    // Unit tangent in the direction of travel. Where the derivative vanishes
+    // (a cubic with a control point on its end point) the chord direction is used.
+    pub fn tangent_at(&self, t: f64) -> Pt<f64> {
+        let tangent = normalize(self.derivative_at(t));
+        if tangent != Pt(0.0, 0.0) {
+            return tangent;
+        }
+
+        let (a, b) = (self.point_at(t - 1e-4), self.point_at(t + 1e-4));
+        let nudged = normalize(Pt(b.0 - a.0, b.1 - a.1));
+        if nudged != Pt(0.0, 0.0) {
+            return nudged;
+        }
+        let (start, end) = (self.start_point(), self.end_point());
+        normalize(Pt(end.0 - start.0, end.1 - start.1))
+    }
+
+    pub fn length(&self) -> f64 {
+        match self {
+            Stroke::Line { from, to } => distance(*from, *to),
+            Stroke::Arc {
+                radius, start, end, ..
+            } => radius * arc_sweep(*start, *end).to_radians().abs(),
+            Stroke::Polyline { points } => points.windows(2).map(|p| distance(p[0], p[1])).sum(),
+            Stroke::Quadratic { .. } | Stroke::Cubic { .. } => integrate(
+                |t| {
+                    let d = self.derivative_at(t);
+                    d.0.hypot(d.1)
+                },
+                16,
+            ),
+        }
+    }
+
+    // Approximate the stroke with a polyline that stays within tolerance of the curve
+    pub fn flatten(&self, tolerance: f64) -> Vec<Pt<f64>> {
+        let tolerance = tolerance.max(1e-6);
+        let segments = match self {
+            Stroke::Line { from, to } => return vec![*from, *to],
+            Stroke::Polyline { points } => return points.clone(),
+            Stroke::Arc {
+                radius, start, end, ..
+            } => {
+                // Largest step whose chord stays within tolerance of the circle
+                let step = if tolerance >= *radius {
+                    std::f64::consts::PI
+                } else {
+                    2.0 * (1.0 - tolerance / radius).acos()
+                };
+                ((end - start).to_radians().abs() / step).ceil()
+            }
+            Stroke::Quadratic { from, ctrl, to } => {
+                let dd = (from.0 - 2.0 * ctrl.0 + to.0).hypot(from.1 - 2.0 * ctrl.1 + to.1);
+                (dd / (4.0 * tolerance)).sqrt().ceil()
+            }
+            Stroke::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => {
+                let dd1 =
+                    (from.0 - 2.0 * ctrl1.0 + ctrl2.0).hypot(from.1 - 2.0 * ctrl1.1 + ctrl2.1);
+                let dd2 = (ctrl1.0 - 2.0 * ctrl2.0 + to.0).hypot(ctrl1.1 - 2.0 * ctrl2.1 + to.1);
+                (3.0 * dd1.max(dd2) / (4.0 * tolerance)).sqrt().ceil()
+            }
+        };
+
+        let segments = (segments as usize).max(1);
+        (0..=segments)
+            .map(|i| self.point_at(i as f64 / segments as f64))
+            .collect()
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        let mut bounds = Bounds::from_point(self.start_point());
+        bounds.include(self.end_point());
+
+        match self {
+            Stroke::Line { .. } => {}
+            Stroke::Polyline { points } => points.iter().for_each(|p| bounds.include(*p)),
+            Stroke::Arc {
+                center,
+                radius,
+                start,
+                end,
+            } => {
+                // Every multiple of 90 degrees the arc passes through is an extreme point
+                let (lo, hi) = if start <= end {
+                    (*start, *end)
+                } else {
+                    (*end, *start)
+                };
+                let mut angle = (lo / 90.0).ceil() * 90.0;
+                while angle <= hi && angle < lo + 360.0 {
+                    let rad = angle.to_radians();
+                    bounds.include(Pt(
+                        center.0 + radius * rad.cos(),
+                        center.1 + radius * rad.sin(),
+                    ));
+                    angle += 90.0;
+                }
+            }
+            Stroke::Quadratic { from, ctrl, to } => {
+                for t in [
+                    quadratic_extrema(from.0, ctrl.0, to.0),
+                    quadratic_extrema(from.1, ctrl.1, to.1),
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    bounds.include(self.point_at(t));
+                }
+            }
+            Stroke::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => {
+                let xs = cubic_extrema(from.0, ctrl1.0, ctrl2.0, to.0);
+                let ys = cubic_extrema(from.1, ctrl1.1, ctrl2.1, to.1);
+                for t in xs.into_iter().chain(ys) {
+                    bounds.include(self.point_at(t));
+                }
+            }
+        }
+
+        bounds
+    }
+
+    // Area covered by the stroke drawn with round caps and joins
+    pub fn ink_bounds(&self, line_width: f64) -> Bounds {
+        self.bounds().expand(line_width / 2.0)
+    }
+
+    // Flatten, then drop points that don't change the shape by more than tolerance
+    pub fn flatten_simplified(&self, tolerance: f64) -> Vec<Pt<f64>> {
+        simplify(&self.flatten(tolerance / 2.0), tolerance / 2.0)
+    }
+}
@@ -0,0 +1,712 @@
+// Runtime parser for .glyphs font files.
+//
+// The grammar is the same one the glyphs! macro in letters.rs accepts:
+//
+//     // comment
+//     metrics {
+//         baseline (0) x_height (100) cap_height (140)
+//         ascender (140) descender (-50) line_gap (30)
+//     }
+//     'c' {
+//         line (x1, y1) -> (x2, y2)
+//         arc (x, y) radius (r) from (start) -> (end)
+//         quad (x1, y1) via (cx, cy) -> (x2, y2)
+//         cubic (x1, y1) via (cx1, cy1) (cx2, cy2) -> (x2, y2)
+//         poly (x1, y1) -> (x2, y2) -> (x3, y3)
+//         restrict_width (w)
+//         anchor top (x, y)             // also bottom, above_right, entry and exit
+//     }
+//     ligature 'f' 'i' { ... }          // drawn instead of "fi", x up to 200
+//     alternate 'e' { ... }             // variant picked at random
+//     alternate 'r' before ('n', 'm') after ('o') { ... }
+//     kern 'r' 'y' (-10)                // in glyph units, negative is closer
+//
+// Values are checked like the macro checks them, metrics in order and points
+// inside the glyph box and between the descender and the ascender.
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::types::{Alternate, Anchor, Font, FontMetrics, Glyph, Ligature, Pt, Stroke};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug)]
+pub enum GlyphFileError {
+    Io(std::io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for GlyphFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GlyphFileError::Io(err) => write!(f, "failed to read glyph file: {err}"),
+            GlyphFileError::Parse(err) => write!(f, "invalid glyph file: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for GlyphFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GlyphFileError::Io(err) => Some(err),
+            GlyphFileError::Parse(err) => Some(err),
+        }
+    }
+}
+
+impl From<std::io::Error> for GlyphFileError {
+    fn from(err: std::io::Error) -> Self {
+        GlyphFileError::Io(err)
+    }
+}
+
+impl From<ParseError> for GlyphFileError {
+    fn from(err: ParseError) -> Self {
+        GlyphFileError::Parse(err)
+    }
+}
+
+pub fn load_font(path: impl AsRef<Path>) -> Result<Font, GlyphFileError> {
+    let source = fs::read_to_string(path)?;
+    Ok(parse_font(&source)?)
+}
+
+pub fn load_glyphs(path: impl AsRef<Path>) -> Result<HashMap<char, Glyph>, GlyphFileError> {
+    Ok(load_font(path)?.glyphs)
+}
+
+pub fn parse_glyphs(source: &str) -> Result<HashMap<char, Glyph>, ParseError> {
+    Ok(parse_font(source)?.glyphs)
+}
+
+// Metrics missing from the file fall back to FontMetrics::default()
+pub fn parse_font(source: &str) -> Result<Font, ParseError> {
+    let tokens = tokenize(source)?;
+    Parser {
+        tokens,
+        pos: 0,
+        end: end_position(source),
+        cells: 1,
+        ys: Vec::new(),
+    }
+    .parse_file()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Char(char),
+    Ident(String),
+    Int(i64),
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
+    Comma,
+    Arrow,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Char(ch) => write!(f, "{ch:?}"),
+            Token::Ident(name) => write!(f, "`{name}`"),
+            Token::Int(n) => write!(f, "`{n}`"),
+            Token::LParen => write!(f, "`(`"),
+            Token::RParen => write!(f, "`)`"),
+            Token::LBrace => write!(f, "`{{`"),
+            Token::RBrace => write!(f, "`}}`"),
+            Token::Comma => write!(f, "`,`"),
+            Token::Arrow => write!(f, "`->`"),
+        }
+    }
+}
+
+// 1-based line and column of a token
+#[derive(Debug, Clone, Copy)]
+struct Span {
+    line: usize,
+    column: usize,
+}
+
+impl Span {
+    fn error(self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            message: message.into(),
+        }
+    }
+}
+
+fn end_position(source: &str) -> Span {
+    let line = source.lines().count().max(1);
+    let column = source.lines().last().map_or(0, |l| l.chars().count()) + 1;
+    Span { line, column }
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, Span)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    let mut line = 1;
+    let mut column = 1;
+
+    // Advance one char, keeping line and column up to date
+    macro_rules! bump {
+        () => {{
+            let ch = chars.next();
+            if ch == Some('\n') {
+                line += 1;
+                column = 1;
+            } else if ch.is_some() {
+                column += 1;
+            }
+            ch
+        }};
+    }
+
+    while let Some(&ch) = chars.peek() {
+        let span = Span { line, column };
+
+        let token = match ch {
+            c if c.is_whitespace() => {
+                bump!();
+                continue;
+            }
+            '/' => {
+                bump!();
+                if chars.peek() != Some(&'/') {
+                    return Err(span.error("unexpected `/`, comments start with `//`"));
+                }
+                while !matches!(chars.peek(), None | Some('\n')) {
+                    bump!();
+                }
+                continue;
+            }
+            '(' | ')' | '{' | '}' | ',' => {
+                bump!();
+                match ch {
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    '{' => Token::LBrace,
+                    '}' => Token::RBrace,
+                    _ => Token::Comma,
+                }
+            }
+            '-' => {
+                bump!();
+                match chars.peek() {
+                    Some('>') => {
+                        bump!();
+                        Token::Arrow
+                    }
+                    Some(c) if c.is_ascii_digit() => {
+                        let mut digits = String::from("-");
+                        while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                            digits.push(c);
+                            bump!();
+                        }
+                        Token::Int(parse_int(&digits, span)?)
+                    }
+                    _ => return Err(span.error("expected `->` or a negative number")),
+                }
+            }
+            c if c.is_ascii_digit() => {
+                let mut digits = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                    digits.push(c);
+                    bump!();
+                }
+                Token::Int(parse_int(&digits, span)?)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_alphanumeric() || **c == '_') {
+                    name.push(c);
+                    bump!();
+                }
+                Token::Ident(name)
+            }
+            '\'' => {
+                bump!();
+                let value = match bump!() {
+                    Some('\\') => match bump!() {
+                        Some('\\') => '\\',
+                        Some('\'') => '\'',
+                        Some('"') => '"',
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        Some('0') => '\0',
+                        Some('u') => {
+                            if bump!() != Some('{') {
+                                return Err(span.error("expected `{` after `\\u`"));
+                            }
+                            let mut hex = String::new();
+                            while let Some(&c) = chars.peek().filter(|c| **c != '}' && **c != '\'')
+                            {
+                                hex.push(c);
+                                bump!();
+                            }
+                            if bump!() != Some('}') {
+                                return Err(span.error("unterminated unicode escape"));
+                            }
+                            u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| {
+                                    span.error(format!("invalid unicode escape `\\u{{{hex}}}`"))
+                                })?
+                        }
+                        Some(c) => {
+                            return Err(span.error(format!("unknown character escape `\\{c}`")))
+                        }
+                        None => return Err(span.error("unterminated character literal")),
+                    },
+                    Some('\n') | None => return Err(span.error("unterminated character literal")),
+                    Some(c) => c,
+                };
+                if bump!() != Some('\'') {
+                    return Err(span.error("character literals must contain exactly one character"));
+                }
+                Token::Char(value)
+            }
+            c => return Err(span.error(format!("unexpected character {c:?}"))),
+        };
+
+        tokens.push((token, span));
+    }
+
+    Ok(tokens)
+}
+
+fn parse_int(digits: &str, span: Span) -> Result<i64, ParseError> {
+    digits
+        .parse()
+        .map_err(|_| span.error(format!("number `{digits}` is too large")))
+}
+
+struct Parser {
+    tokens: Vec<(Token, Span)>,
+    pos: usize,
+    end: Span,  // position reported for errors at end of input
+    cells: i64, // glyph widths the glyph being parsed may use, more for ligatures
+    // y of every point, checked against the metrics once the whole file is read
+    ys: Vec<(i16, Span)>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&(Token, Span)> {
+        self.tokens.get(self.pos)
+    }
+
+    fn span(&self) -> Span {
+        self.peek().map_or(self.end, |(_, span)| *span)
+    }
+
+    fn next(&mut self, expected: &str) -> Result<(Token, Span), ParseError> {
+        match self.tokens.get(self.pos) {
+            Some(token) => {
+                self.pos += 1;
+                Ok(token.clone())
+            }
+            None => Err(self
+                .end
+                .error(format!("expected {expected}, found end of file"))),
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), ParseError> {
+        let (token, span) = self.next(&expected.to_string())?;
+        if token != expected {
+            return Err(span.error(format!("expected {expected}, found {token}")));
+        }
+        Ok(())
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Result<(), ParseError> {
+        self.expect(Token::Ident(keyword.to_string()))
+    }
+
+    fn int<T: TryFrom<i64>>(&mut self, what: &str) -> Result<T, ParseError> {
+        match self.next(what)? {
+            (Token::Int(n), span) => {
+                T::try_from(n).map_err(|_| span.error(format!("{what} `{n}` is out of range")))
+            }
+            (token, span) => Err(span.error(format!("expected {what}, found {token}"))),
+        }
+    }
+
+    // `(a)`
+    fn value<T: TryFrom<i64>>(&mut self, what: &str) -> Result<T, ParseError> {
+        self.expect(Token::LParen)?;
+        let value = self.int(what)?;
+        self.expect(Token::RParen)?;
+        Ok(value)
+    }
+
+    // `(x, y)`, x has to be in the glyph box
+    fn point(&mut self) -> Result<Pt, ParseError> {
+        self.expect(Token::LParen)?;
+        let x_span = self.span();
+        let x: i16 = self.int("x coordinate")?;
+        let max = 100 * self.cells;
+        if !(0..=max).contains(&(x as i64)) {
+            return Err(x_span.error(format!(
+                "x coordinate `{x}` is outside the 0-{max} glyph box"
+            )));
+        }
+        self.expect(Token::Comma)?;
+        let y_span = self.span();
+        let y = self.int("y coordinate")?;
+        self.ys.push((y, y_span));
+        self.expect(Token::RParen)?;
+        Ok(Pt(x, y))
+    }
+
+    // `'c'`
+    fn char(&mut self) -> Result<char, ParseError> {
+        match self.next("a character")? {
+            (Token::Char(ch), _) => Ok(ch),
+            (token, span) => Err(span.error(format!("expected a character, found {token}"))),
+        }
+    }
+
+    // `('a', 'b')`
+    fn char_list(&mut self) -> Result<Vec<char>, ParseError> {
+        self.expect(Token::LParen)?;
+        let mut chars = vec![self.char()?];
+        while matches!(self.peek(), Some((Token::Comma, _))) {
+            self.pos += 1;
+            chars.push(self.char()?);
+        }
+        self.expect(Token::RParen)?;
+        Ok(chars)
+    }
+
+    fn parse_file(&mut self) -> Result<Font, ParseError> {
+        let mut metrics = None;
+        let mut glyphs = HashMap::new();
+        let mut ligatures: Vec<Ligature> = Vec::new();
+        let mut alternates: HashMap<char, Vec<Alternate>> = HashMap::new();
+        // Alternates can come before the glyph they replace, checked at the end
+        let mut alternate_spans = Vec::new();
+        let mut kerning = HashMap::new();
+
+        while self.peek().is_some() {
+            let expected = "a glyph character, `metrics`, `ligature`, `alternate` or `kern`";
+            let (ch, span) = match self.next(expected)? {
+                (Token::Char(ch), span) => (ch, span),
+                (Token::Ident(name), span) if name == "metrics" => {
+                    if metrics.is_some() {
+                        return Err(span.error("metrics are already defined"));
+                    }
+                    metrics = Some(self.parse_metrics()?);
+                    continue;
+                }
+                (Token::Ident(name), span) if name == "ligature" => {
+                    let mut chars = Vec::new();
+                    while let Some((Token::Char(ch), _)) = self.peek() {
+                        chars.push(*ch);
+                        self.pos += 1;
+                    }
+                    if chars.len() < 2 {
+                        return Err(span.error("a ligature needs at least two characters"));
+                    }
+                    if ligatures.iter().any(|l| l.chars == chars) {
+                        let text: String = chars.iter().collect();
+                        return Err(
+                            span.error(format!("ligature {text:?} is defined more than once"))
+                        );
+                    }
+                    self.cells = chars.len() as i64;
+                    let glyph = self.parse_glyph(chars[0])?;
+                    self.cells = 1;
+                    ligatures.push(Ligature { chars, glyph });
+                    continue;
+                }
+                (Token::Ident(name), span) if name == "alternate" => {
+                    let ch = self.char()?;
+                    let (mut before, mut after) = (Vec::new(), Vec::new());
+                    while let Some((Token::Ident(name), _)) = self.peek() {
+                        let context = match name.as_str() {
+                            "before" => &mut before,
+                            "after" => &mut after,
+                            _ => break,
+                        };
+                        self.pos += 1;
+                        context.extend(self.char_list()?);
+                    }
+                    let glyph = self.parse_glyph(ch)?;
+                    alternates.entry(ch).or_default().push(Alternate {
+                        glyph,
+                        before,
+                        after,
+                    });
+                    alternate_spans.push((ch, span));
+                    continue;
+                }
+                (Token::Ident(name), span) if name == "kern" => {
+                    let pair = (self.char()?, self.char()?);
+                    let value_span = self.span();
+                    let value: i16 = self.value("kerning")?;
+                    if !(-100..=100).contains(&value) {
+                        return Err(value_span.error(format!(
+                            "kerning must be between -100 and 100, found `{value}`"
+                        )));
+                    }
+                    if kerning.insert(pair, value).is_some() {
+                        let (left, right) = pair;
+                        return Err(span.error(format!(
+                            "kerning for {left:?} {right:?} is defined more than once"
+                        )));
+                    }
+                    continue;
+                }
+                (token, span) => {
+                    return Err(span.error(format!("expected {expected}, found {token}")))
+                }
+            };
+            if glyphs.contains_key(&ch) {
+                return Err(span.error(format!("glyph {ch:?} is defined more than once")));
+            }
+
+            let glyph = self.parse_glyph(ch)?;
+            glyphs.insert(ch, glyph);
+        }
+
+        if let Some((ch, span)) = alternate_spans
+            .into_iter()
+            .find(|(ch, _)| !glyphs.contains_key(ch))
+        {
+            return Err(span.error(format!("alternate for {ch:?}, which has no glyph")));
+        }
+
+        let metrics = metrics.unwrap_or_default();
+        let range = metrics.descender..=metrics.ascender;
+        if let Some((y, span)) = self.ys.iter().find(|(y, _)| !range.contains(y)) {
+            return Err(span.error(format!(
+                "y coordinate `{y}` is outside the descender-ascender range {}..={}",
+                range.start(),
+                range.end()
+            )));
+        }
+
+        Ok(Font {
+            metrics,
+            glyphs,
+            ligatures,
+            alternates,
+            kerning,
+        })
+    }
+
+    fn parse_metrics(&mut self) -> Result<FontMetrics, ParseError> {
+        let mut metrics = FontMetrics::default();
+        // Where each metric was written, for pointing at the ones out of order
+        let mut spans: HashMap<&str, Span> = HashMap::new();
+        let block = self.span();
+
+        self.expect(Token::LBrace)?;
+        loop {
+            let span = self.span();
+            let (name, field) = match self.next("a metric or `}`")?.0 {
+                Token::RBrace => break,
+                Token::Ident(name) => match name.as_str() {
+                    "baseline" => ("baseline", &mut metrics.baseline),
+                    "x_height" => ("x_height", &mut metrics.x_height),
+                    "cap_height" => ("cap_height", &mut metrics.cap_height),
+                    "ascender" => ("ascender", &mut metrics.ascender),
+                    "descender" => ("descender", &mut metrics.descender),
+                    "line_gap" => ("line_gap", &mut metrics.line_gap),
+                    _ => return Err(span.error(format!("unknown metric `{name}`"))),
+                },
+                token => {
+                    return Err(span.error(format!("expected a metric or `}}`, found {token}")))
+                }
+            };
+            if spans.insert(name, span).is_some() {
+                return Err(span.error(format!("metric `{name}` is defined more than once")));
+            }
+            *field = self.value("metric")?;
+        }
+
+        // descender <= baseline < x_height <= cap_height <= ascender, like the glyphs! macro
+        let order = [
+            (
+                "descender",
+                metrics.descender,
+                "baseline",
+                metrics.baseline,
+                false,
+            ),
+            (
+                "baseline",
+                metrics.baseline,
+                "x_height",
+                metrics.x_height,
+                true,
+            ),
+            (
+                "x_height",
+                metrics.x_height,
+                "cap_height",
+                metrics.cap_height,
+                false,
+            ),
+            (
+                "cap_height",
+                metrics.cap_height,
+                "ascender",
+                metrics.ascender,
+                false,
+            ),
+        ];
+        for (lower, low, upper, high, strict) in order {
+            if low > high || (strict && low == high) {
+                let relation = if strict { "above" } else { "at or above" };
+                let span = spans
+                    .get(upper)
+                    .or_else(|| spans.get(lower))
+                    .unwrap_or(&block);
+                return Err(span.error(format!(
+                    "{upper} ({high}) must be {relation} {lower} ({low})"
+                )));
+            }
+        }
+        if metrics.line_gap < 0 {
+            return Err(spans["line_gap"].error("line_gap can't be negative"));
+        }
+
+        Ok(metrics)
+    }
+
+    fn parse_glyph(&mut self, ch: char) -> Result<Glyph, ParseError> {
+        let mut strokes = Vec::new();
+        let mut width_restriction = None;
+        let mut anchors = BTreeMap::new();
+
+        self.expect(Token::LBrace)?;
+        loop {
+            let span = self.span();
+            match self.next("a stroke or `}`")?.0 {
+                Token::RBrace => break,
+                Token::Ident(name) if name == "line" => {
+                    let from = self.point()?;
+                    self.expect(Token::Arrow)?;
+                    let to = self.point()?;
+                    strokes.push(Stroke::Line { from, to });
+                }
+                Token::Ident(name) if name == "arc" => {
+                    let center = self.point()?;
+                    self.keyword("radius")?;
+                    let radius_span = self.span();
+                    let radius = self.value("radius")?;
+                    if radius <= 0 {
+                        return Err(radius_span
+                            .error(format!("radius must be positive, found `{radius}`")));
+                    }
+                    self.keyword("from")?;
+                    let start = self.value("start angle")?;
+                    self.expect(Token::Arrow)?;
+                    let end = self.value("end angle")?;
+                    strokes.push(Stroke::Arc {
+                        center,
+                        radius,
+                        start,
+                        end,
+                    });
+                }
+                Token::Ident(name) if name == "quad" => {
+                    let from = self.point()?;
+                    self.keyword("via")?;
+                    let ctrl = self.point()?;
+                    self.expect(Token::Arrow)?;
+                    let to = self.point()?;
+                    strokes.push(Stroke::Quadratic { from, ctrl, to });
+                }
+                Token::Ident(name) if name == "cubic" => {
+                    let from = self.point()?;
+                    self.keyword("via")?;
+                    let ctrl1 = self.point()?;
+                    let ctrl2 = self.point()?;
+                    self.expect(Token::Arrow)?;
+                    let to = self.point()?;
+                    strokes.push(Stroke::Cubic {
+                        from,
+                        ctrl1,
+                        ctrl2,
+                        to,
+                    });
+                }
+                Token::Ident(name) if name == "poly" => {
+                    let mut points = vec![self.point()?];
+                    while matches!(self.peek(), Some((Token::Arrow, _))) {
+                        self.pos += 1;
+                        points.push(self.point()?);
+                    }
+                    if points.len() < 2 {
+                        return Err(span.error("poly needs at least two points"));
+                    }
+                    strokes.push(Stroke::Polyline { points });
+                }
+                Token::Ident(name) if name == "restrict_width" => {
+                    let width_span = self.span();
+                    let width = self.value("width")?;
+                    if width > 100 {
+                        return Err(width_span.error(format!(
+                            "restrict_width must be between 0 and 100, found `{width}`"
+                        )));
+                    }
+                    width_restriction = Some(width);
+                }
+                Token::Ident(name) if name == "anchor" => {
+                    let span = self.span();
+                    let name = match self.next("an anchor name")?.0 {
+                        Token::Ident(name) => name,
+                        token => {
+                            return Err(span.error(format!("expected an anchor name, found {token}")))
+                        }
+                    };
+                    let anchor = match name.as_str() {
+                        "top" => Anchor::Top,
+                        "bottom" => Anchor::Bottom,
+                        "above_right" => Anchor::AboveRight,
+                        "entry" => Anchor::Entry,
+                        "exit" => Anchor::Exit,
+                        _ => return Err(span.error(format!("unknown anchor `{name}`"))),
+                    };
+                    if anchors.insert(anchor, self.point()?).is_some() {
+                        return Err(span.error(format!("anchor `{name}` is defined more than once")));
+                    }
+                }
+                token => {
+                    return Err(span.error(format!(
+                        "expected `line`, `arc`, `quad`, `cubic`, `poly`, `restrict_width`, `anchor` or `}}`, found {token}"
+                    )))
+                }
+            }
+        }
+
+        Ok(Glyph {
+            ch,
+            strokes,
+            width_restriction,
+            anchors,
+        })
+    }
+}
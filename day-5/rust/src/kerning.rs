@@ -0,0 +1,254 @@
+// Kerning, spacing adjustments for pairs of glyphs.
+//
+// The automatic pass cuts both glyphs into horizontal bands and measures the
+// gap between the right edge of the left glyph's ink and the left edge of the
+// right one's in every band. The nearest band between the baseline and the
+// x-height is moved to the gap two stems have, then the pair is pulled in a
+// bit more for the space in the other bands, like round sides, the open side
+// of r or the arms of v and y, counting each only up to a depth. The closest
+// band overall keeps the glyphs from running into each other.
+// Values are in glyph units, 100 is a glyph's width, negative pulls closer.
+use std::collections::HashMap;
+
+use crate::layout::{place_strokes, GLYPH_SIZE, GLYPH_SPACING};
+use crate::marks;
+use crate::types::{Font, Glyph};
+
+// Pairs closer to zero than this are left out of the table
+const MIN_KERN: f64 = 0.5;
+
+#[derive(Debug, Clone)]
+pub struct KerningConfig {
+    pub spacing: f64,    // between glyphs before width_restriction, glyph units
+    pub target: f64,     // gap at the nearest band, two stems have spacing
+    pub max_depth: f64,  // how much further than the nearest band a band counts
+    pub min_gap: f64,    // closest the ink of two glyphs may come
+    pub strength: f64,   // 0-1, how much the space in the other bands pulls in
+    pub max_adjust: f64, // largest adjustment either way
+    pub band: f64,       // height of the bands
+    pub tolerance: f64,  // for flattening curves
+}
+
+impl Default for KerningConfig {
+    fn default() -> Self {
+        let spacing = GLYPH_SPACING / GLYPH_SIZE * 100.0;
+        Self {
+            spacing,
+            target: spacing,
+            max_depth: 25.0,
+            min_gap: 15.0,
+            strength: 0.5,
+            max_adjust: 60.0,
+            band: 5.0,
+            tolerance: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KerningTable {
+    pub pairs: HashMap<(char, char), f64>,
+}
+
+impl KerningTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Only the pairs the font sets itself
+    pub fn from_font(font: &Font) -> Self {
+        let pairs = font
+            .kerning
+            .iter()
+            .map(|(&pair, &value)| (pair, value as f64))
+            .collect();
+        Self { pairs }
+    }
+
+    // Every pair of the font's glyphs measured, except combining marks which
+    // go on other glyphs. The font's own pairs win.
+    pub fn auto(font: &Font, config: &KerningConfig) -> Self {
+        let bands = bands(font, config.band);
+        let profiles: Vec<(&Glyph, Profile)> = font
+            .glyphs
+            .values()
+            .filter(|glyph| !marks::is_mark(glyph.ch))
+            .map(|glyph| (glyph, Profile::new(glyph, &bands, config.tolerance)))
+            .filter(|(_, profile)| profile.has_core_ink(&bands))
+            .collect();
+
+        let mut table = Self::new();
+        for (left, left_profile) in &profiles {
+            for (right, right_profile) in &profiles {
+                let value = pair_kerning(config, &bands, left, left_profile, right_profile);
+                if value.abs() >= MIN_KERN {
+                    table.set(left.ch, right.ch, value);
+                }
+            }
+        }
+        for (&(left, right), &value) in &font.kerning {
+            table.set(left, right, value as f64);
+        }
+        table
+    }
+
+    pub fn get(&self, left: char, right: char) -> f64 {
+        self.pairs.get(&(left, right)).copied().unwrap_or(0.0)
+    }
+
+    pub fn set(&mut self, left: char, right: char, value: f64) {
+        self.pairs.insert((left, right), value);
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
+
+// Adjustment for the pair, already clamped and kept clear of collisions
+pub fn kern_pair(font: &Font, left: &Glyph, right: &Glyph, config: &KerningConfig) -> f64 {
+    let bands = bands(font, config.band);
+    let left_profile = Profile::new(left, &bands, config.tolerance);
+    let right_profile = Profile::new(right, &bands, config.tolerance);
+    if !left_profile.has_core_ink(&bands) || !right_profile.has_core_ink(&bands) {
+        return 0.0;
+    }
+    pair_kerning(config, &bands, left, &left_profile, &right_profile)
+}
+
+fn pair_kerning(
+    config: &KerningConfig,
+    bands: &[Band],
+    left: &Glyph,
+    l: &Profile,
+    r: &Profile,
+) -> f64 {
+    let restriction = left.width_restriction.unwrap_or(100) as f64 / 100.0;
+    let spacing = config.spacing * restriction;
+
+    // Bands where one of them has no ink have an endless gap
+    let mut closest = f64::INFINITY;
+    let mut core = Vec::new();
+    for ((l, r), band) in l.extents.iter().zip(&r.extents).zip(bands) {
+        let gap = match (l, r) {
+            (Some((_, l_max)), Some((r_min, _))) => 100.0 - l_max + r_min + spacing,
+            _ => f64::INFINITY,
+        };
+        closest = closest.min(gap);
+        if band.core {
+            core.push(gap);
+        }
+    }
+    let nearest = core.iter().copied().fold(f64::INFINITY, f64::min);
+    if !nearest.is_finite() {
+        return 0.0;
+    }
+
+    let depth = core
+        .iter()
+        .map(|gap| (gap - nearest).min(config.max_depth))
+        .sum::<f64>()
+        / core.len() as f64;
+    let value = config.target - nearest - config.strength * depth;
+    value
+        .max(config.min_gap - closest)
+        .clamp(-config.max_adjust, config.max_adjust)
+}
+
+struct Band {
+    bottom: f64,
+    top: f64,
+    core: bool, // between the baseline and the x-height
+}
+
+// Bands of about height from the descender to the ascender
+fn bands(font: &Font, height: f64) -> Vec<Band> {
+    let metrics = &font.metrics;
+    let (bottom, top) = (metrics.descender as f64, metrics.ascender as f64);
+    let count = ((top - bottom) / height).ceil().max(1.0) as usize;
+    let height = (top - bottom) / count as f64;
+    (0..count)
+        .map(|i| {
+            let (lo, hi) = (bottom + i as f64 * height, bottom + (i + 1) as f64 * height);
+            Band {
+                bottom: lo,
+                top: hi,
+                core: lo >= metrics.baseline as f64 && hi <= metrics.x_height as f64,
+            }
+        })
+        .collect()
+}
+
+// Left and right edge of a glyph's ink in every band
+struct Profile {
+    extents: Vec<Option<(f64, f64)>>,
+}
+
+impl Profile {
+    fn new(glyph: &Glyph, bands: &[Band], tolerance: f64) -> Self {
+        // Spaces count as filling their box, so the letters next to them
+        // are spaced like they are next to a stem
+        if glyph.strokes.is_empty() {
+            return Self {
+                extents: vec![Some((0.0, 100.0)); bands.len()],
+            };
+        }
+
+        let mut extents = vec![None; bands.len()];
+        let mut include = |band: usize, x: f64| {
+            let extent: &mut Option<(f64, f64)> = &mut extents[band];
+            *extent = Some(match *extent {
+                Some((min, max)) => (f64::min(min, x), f64::max(max, x)),
+                None => (x, x),
+            });
+        };
+
+        for stroke in place_strokes(&glyph.strokes, 0.0, 0.0, 100.0, 100.0) {
+            let points = stroke.flatten(tolerance);
+            if let [p] = points[..] {
+                if let Some(band) = bands.iter().position(|b| p.1 >= b.bottom && p.1 <= b.top) {
+                    include(band, p.0);
+                }
+            }
+            for pair in points.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                for (
+                    band,
+                    &Band {
+                        bottom: lo,
+                        top: hi,
+                        ..
+                    },
+                ) in bands.iter().enumerate()
+                {
+                    // The part of the segment inside the band
+                    let (y_min, y_max) = (a.1.min(b.1), a.1.max(b.1));
+                    if y_max < lo || y_min > hi {
+                        continue;
+                    }
+                    if y_max - y_min < 1e-9 {
+                        include(band, a.0);
+                        include(band, b.0);
+                        continue;
+                    }
+                    let x_at = |y: f64| a.0 + (y - a.1) / (b.1 - a.1) * (b.0 - a.0);
+                    include(band, x_at(y_min.max(lo)));
+                    include(band, x_at(y_max.min(hi)));
+                }
+            }
+        }
+
+        Self { extents }
+    }
+
+    fn has_core_ink(&self, bands: &[Band]) -> bool {
+        self.extents
+            .iter()
+            .zip(bands)
+            .any(|(extent, band)| band.core && extent.is_some())
+    }
+}
@@ -0,0 +1,532 @@
+use std::ops::Range;
+
+use crate::cursive;
+use crate::kerning::KerningTable;
+use crate::marks::{self, Composed};
+use crate::substitution;
+use crate::transform::Transform;
+use crate::types::{Font, FontMetrics, Glyph, Pt, Stroke};
+
+// Standard spacing between glyphs, same as the ruby GlyphDSL
+pub const GLYPH_SPACING: f64 = 20.0;
+
+// Default glyph box size at scale 1.0
+pub const GLYPH_SIZE: f64 = 40.0;
+
+// A glyph placed on the page, strokes are in output coordinates
+#[derive(Debug, Clone)]
+pub struct PlacedGlyph {
+    pub ch: char,
+    pub index: usize, // char index in the source text
+    pub line: usize,
+    pub x: f64,
+    pub y: f64, // baseline
+    pub advance: f64,
+    pub width: f64, // of the glyph box, a glyph width per char for ligatures
+    pub angle: f64, // rotation in degrees, only text on a path turns glyphs
+    pub strokes: Vec<Stroke<f64>>,
+}
+
+// A character that had no glyph in the glyph map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingGlyph {
+    pub index: usize,
+    pub ch: char,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+    Right,
+    // Stretch the spaces of wrapped lines to fill max_width, the last line
+    // of a paragraph stays left aligned
+    Justify,
+}
+
+// One line of laid out text
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutLine {
+    pub glyphs: Range<usize>, // into LayoutResult::glyphs
+    pub x: f64,
+    pub baseline: f64,
+    pub width: f64, // from x to the right edge of the last visible glyph
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LayoutResult {
+    pub glyphs: Vec<PlacedGlyph>,
+    pub lines: Vec<LayoutLine>,
+    pub missing: Vec<MissingGlyph>,
+    pub width: f64, // of the widest line
+    pub line_height: f64,
+    pub ascent: f64,  // above the baseline
+    pub descent: f64, // below the baseline, positive
+}
+
+impl LayoutResult {
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    // All strokes in drawing order
+    pub fn strokes(&self) -> impl Iterator<Item = &Stroke<f64>> {
+        self.glyphs.iter().flat_map(|g| g.strokes.iter())
+    }
+
+    // From the ascender of the first line to the descender of the last
+    pub fn height(&self) -> f64 {
+        match self.lines.len() {
+            0 => 0.0,
+            n => self.ascent + self.descent + (n - 1) as f64 * self.line_height,
+        }
+    }
+
+    // The glyph whose advance cell contains the point, lines span ascent to descent
+    pub fn hit_test(&self, x: f64, y: f64) -> Option<&PlacedGlyph> {
+        let line = self
+            .lines
+            .iter()
+            .find(|line| y <= line.baseline + self.ascent && y >= line.baseline - self.descent)?;
+        self.glyphs[line.glyphs.clone()]
+            .iter()
+            .find(|glyph| x >= glyph.x && x < glyph.x + glyph.advance)
+    }
+}
+
+// A glyph waiting to be placed
+struct Item<'a> {
+    index: usize,
+    ch: char,
+    glyph: Composed<'a>,
+    advance: f64,
+    width: f64, // of the box, a glyph width per char for ligatures
+    last: char, // the last char a ligature stands for, kerning goes by it
+}
+
+// Text layout, a port of the ruby TextLayout that also does paragraphs. y is
+// the baseline of the first line, '\n' starts a new line and with a max_width
+// set lines wrap at spaces.
+#[derive(Debug, Clone)]
+pub struct TextLayout {
+    pub x: f64,
+    pub y: f64,
+    pub scale: f64,
+    pub spacing_factor: f64,
+    pub glyph_width: f64,
+    pub glyph_height: f64,
+    pub max_width: Option<f64>,
+    pub align: Align,
+    pub line_height: Option<f64>, // baseline to baseline, defaults to the font's
+    // Applied to every glyph around its origin on the baseline, e.g. a skew for italics
+    pub transform: Option<Transform>,
+    pub tolerance: f64, // for arcs the transform has to flatten
+    // Picks among a font's glyph variants, without one the plain glyph is used
+    pub seed: Option<u64>,
+    pub kerning: Option<KerningTable>, // adjusts the advance between pairs
+    // Joins the glyphs of a word through their entry and exit, see cursive
+    pub cursive: bool,
+}
+
+impl TextLayout {
+    pub fn new(x: f64, y: f64, scale: f64, spacing_factor: f64) -> Self {
+        Self {
+            x,
+            y,
+            scale,
+            spacing_factor,
+            glyph_width: GLYPH_SIZE,
+            glyph_height: GLYPH_SIZE,
+            max_width: None,
+            align: Align::Left,
+            line_height: None,
+            transform: None,
+            tolerance: 0.1,
+            seed: None,
+            kerning: None,
+            cursive: false,
+        }
+    }
+
+    pub fn max_width(mut self, max_width: f64) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn line_height(mut self, line_height: f64) -> Self {
+        self.line_height = Some(line_height);
+        self
+    }
+
+    pub fn transform(mut self, transform: Transform) -> Self {
+        self.transform = Some(transform);
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn kerning(mut self, kerning: KerningTable) -> Self {
+        self.kerning = Some(kerning);
+        self
+    }
+
+    pub fn cursive(mut self, cursive: bool) -> Self {
+        self.cursive = cursive;
+        self
+    }
+
+    // Horizontal advance for a glyph. Every glyph is glyph_width wide and
+    // width_restriction scales the spacing that follows it, like the ruby version.
+    // A transform that condenses or expands the glyphs does the same to both.
+    pub fn advance(&self, glyph: &Glyph) -> f64 {
+        let width_restriction = glyph.width_restriction.unwrap_or(100) as f64;
+        let base_spacing = GLYPH_SPACING * self.spacing_factor * self.scale * self.x_scale();
+        self.cell_width() + base_spacing * (width_restriction / 100.0)
+    }
+
+    // Width of a glyph's box on the page
+    pub fn cell_width(&self) -> f64 {
+        self.glyph_width * self.scale * self.x_scale()
+    }
+
+    fn x_scale(&self) -> f64 {
+        self.transform.map_or(1.0, |t| t.x_scale())
+    }
+
+    // Baseline to baseline distance in output units
+    pub fn line_height_for(&self, metrics: &FontMetrics) -> f64 {
+        self.line_height
+            .unwrap_or(metrics.line_height() as f64 / 100.0 * self.glyph_height * self.scale)
+    }
+
+    pub fn layout(&self, font: &Font, text: &str) -> LayoutResult {
+        let height = self.glyph_height * self.scale;
+        let metric = |value: i16| (value as f64 - font.metrics.baseline as f64) / 100.0 * height;
+        let mut result = LayoutResult {
+            line_height: self.line_height_for(&font.metrics),
+            ascent: metric(font.metrics.ascender),
+            descent: -metric(font.metrics.descender),
+            ..Default::default()
+        };
+
+        // Wrap every paragraph first, alignment needs the widest line when there's no max_width
+        let mut lines = Vec::new();
+        let mut index = 0;
+        for paragraph in text.split('\n') {
+            let mut items: Vec<Item> = Vec::new();
+            let chars: Vec<char> = paragraph.chars().collect();
+            let mut i = 0;
+            while i < chars.len() {
+                let ch = chars[i];
+                // A combining mark typed after its base goes on the glyph before it
+                let mark = marks::mark_glyph(font, ch);
+                if let (Some(mark), Some(item)) = (mark, items.last_mut()) {
+                    item.glyph.push_mark(font, mark);
+                    i += 1;
+                    index += 1;
+                    continue;
+                }
+
+                let (glyph, len) = match substitution::ligature(font, &chars[i..]) {
+                    Some(ligature) => (Some(Composed::new(&ligature.glyph)), ligature.chars.len()),
+                    None => {
+                        let previous = i.checked_sub(1).map(|p| chars[p]);
+                        let next = chars.get(i + 1).copied();
+                        let random = self.seed.map(|seed| substitution::random(seed, index));
+                        let glyph = font
+                            .glyphs
+                            .get(&ch)
+                            .map(|g| {
+                                Composed::new(substitution::alternate(
+                                    font, g, previous, next, random,
+                                ))
+                            })
+                            .or_else(|| marks::compose(font, ch));
+                        (glyph, 1)
+                    }
+                };
+                // Ligatures take the room of all their chars
+                let cells = (len - 1) as f64 * self.cell_width();
+                match glyph {
+                    Some(glyph) => {
+                        if let (Some(kerning), Some(previous)) = (&self.kerning, items.last_mut()) {
+                            previous.advance +=
+                                kerning.get(previous.last, ch) / 100.0 * self.cell_width();
+                        }
+                        items.push(Item {
+                            index,
+                            ch,
+                            advance: self.advance(glyph.base) + cells,
+                            width: self.cell_width() + cells,
+                            last: chars[i + len - 1],
+                            glyph,
+                        })
+                    }
+                    None => result.missing.push(MissingGlyph { index, ch }),
+                }
+                i += len;
+                index += len;
+            }
+            index += 1; // the newline
+
+            let wrapped = self.wrap(items);
+            let count = wrapped.len();
+            lines.extend(
+                wrapped
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, line)| (line, i + 1 == count)),
+            );
+        }
+
+        let box_width = self.max_width.unwrap_or_else(|| {
+            lines
+                .iter()
+                .map(|(line, _)| self.measure(line))
+                .fold(0.0, f64::max)
+        });
+        // Glyph y 0 is the bottom of the box, move it so the font baseline lands on the line
+        let box_offset = -(font.metrics.baseline as f64) / 100.0 * height;
+
+        for (number, (line, last)) in lines.into_iter().enumerate() {
+            let baseline = self.y - number as f64 * result.line_height;
+            let width = self.measure(&line);
+            let slack = (box_width - width).max(0.0);
+            let words = trimmed(&line);
+            let gaps = line[words.clone()]
+                .iter()
+                .filter(|item| item.ch == ' ')
+                .count();
+
+            let (offset, stretch) = match self.align {
+                Align::Left => (0.0, 0.0),
+                Align::Center => (slack / 2.0, 0.0),
+                Align::Right => (slack, 0.0),
+                Align::Justify if !last && gaps > 0 => (0.0, slack / gaps as f64),
+                Align::Justify => (0.0, 0.0),
+            };
+
+            let first = result.glyphs.len();
+            let mut joints = Vec::new();
+            let mut current_x = self.x + offset;
+            for (i, item) in line.into_iter().enumerate() {
+                let advance = if item.ch == ' ' && words.contains(&i) {
+                    item.advance + stretch
+                } else {
+                    item.advance
+                };
+                if self.cursive {
+                    joints.push(self.glyph_joint(
+                        font,
+                        item.glyph.base,
+                        current_x,
+                        baseline,
+                        box_offset,
+                    ));
+                }
+                result.glyphs.push(PlacedGlyph {
+                    ch: item.ch,
+                    index: item.index,
+                    line: number,
+                    x: current_x,
+                    y: baseline,
+                    advance,
+                    width: item.width,
+                    angle: 0.0,
+                    strokes: self.glyph_strokes(font, &item.glyph, current_x, baseline, box_offset),
+                });
+                current_x += advance;
+            }
+            if self.cursive {
+                cursive::join(&mut result.glyphs[first..], &joints);
+            }
+
+            let width = if stretch > 0.0 { box_width } else { width };
+            result.width = result.width.max(width);
+            result.lines.push(LayoutLine {
+                glyphs: first..result.glyphs.len(),
+                x: self.x + offset,
+                baseline,
+                width,
+            });
+        }
+
+        result
+    }
+
+    fn glyph_strokes(
+        &self,
+        font: &Font,
+        glyph: &Composed,
+        x: f64,
+        baseline: f64,
+        box_offset: f64,
+    ) -> Vec<Stroke<f64>> {
+        let (width, height) = (
+            self.glyph_width * self.scale,
+            self.glyph_height * self.scale,
+        );
+        let y = baseline + box_offset;
+        let mut strokes = place_strokes(&glyph.base.strokes, x, y, width, height);
+        // Marks are in glyph units too, moved onto their anchors
+        for (mark, offset) in glyph.marks.iter().zip(glyph.mark_offsets(font)) {
+            strokes.extend(place_strokes(
+                &mark.strokes,
+                x + offset.0 / 100.0 * width,
+                y + offset.1 / 100.0 * height,
+                width,
+                height,
+            ));
+        }
+        match self.transform {
+            Some(transform) => transform
+                .around(Pt(x, baseline))
+                .apply_strokes(&strokes, self.tolerance),
+            None => strokes,
+        }
+    }
+
+    // Entry and exit of a glyph in output coordinates
+    fn glyph_joint(
+        &self,
+        font: &Font,
+        glyph: &Glyph,
+        x: f64,
+        baseline: f64,
+        box_offset: f64,
+    ) -> Option<(Pt<f64>, Pt<f64>)> {
+        let (width, height) = (
+            self.glyph_width * self.scale,
+            self.glyph_height * self.scale,
+        );
+        let y = baseline + box_offset;
+        let place = |p: Pt<f64>| {
+            let p = Pt(x + p.0 / 100.0 * width, y + p.1 / 100.0 * height);
+            match self.transform {
+                Some(transform) => transform.around(Pt(x, baseline)).apply(p),
+                None => p,
+            }
+        };
+        Some((
+            place(cursive::entry(font, glyph)?),
+            place(cursive::exit(font, glyph)?),
+        ))
+    }
+
+    // Greedy wrap at spaces. Spaces where a line breaks are dropped and a word
+    // wider than max_width gets a line of its own.
+    fn wrap<'a>(&self, items: Vec<Item<'a>>) -> Vec<Vec<Item<'a>>> {
+        let Some(max_width) = self.max_width else {
+            return vec![items];
+        };
+
+        let mut lines = Vec::new();
+        let mut line: Vec<Item> = Vec::new();
+        let mut items = items.into_iter().peekable();
+        while items.peek().is_some() {
+            let mut spaces = Vec::new();
+            while let Some(item) = items.next_if(|item| item.ch == ' ') {
+                spaces.push(item);
+            }
+            let mut word = Vec::new();
+            while let Some(item) = items.next_if(|item| item.ch != ' ') {
+                word.push(item);
+            }
+
+            let before = line.len();
+            line.extend(spaces);
+            line.extend(word);
+            if before > 0 && self.measure(&line) > max_width {
+                let word = line
+                    .split_off(before)
+                    .into_iter()
+                    .skip_while(|item| item.ch == ' ')
+                    .collect();
+                lines.push(std::mem::replace(&mut line, word));
+            }
+        }
+        lines.push(line);
+        lines
+    }
+
+    // Width up to the right edge of the last glyph, ignoring trailing spaces
+    fn measure(&self, line: &[Item]) -> f64 {
+        match trimmed(line).end {
+            0 => 0.0,
+            n => line[..n - 1].iter().map(|item| item.advance).sum::<f64>() + line[n - 1].width,
+        }
+    }
+}
+
+// The line without leading and trailing spaces
+fn trimmed(line: &[Item]) -> Range<usize> {
+    let end = line.len() - line.iter().rev().take_while(|item| item.ch == ' ').count();
+    let start = line[..end].iter().take_while(|item| item.ch == ' ').count();
+    start..end
+}
+
+// Convert normalized (0-100) strokes to output coordinates in a width x height box at x, y.
+// Coordinates outside 0-100 (ascenders, descenders) end up outside the box.
+pub fn place_strokes(
+    strokes: &[Stroke],
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+) -> Vec<Stroke<f64>> {
+    let pt = |p: &Pt| {
+        Pt(
+            x + (p.0 as f64 / 100.0) * width,
+            y + (p.1 as f64 / 100.0) * height,
+        )
+    };
+
+    strokes
+        .iter()
+        .map(|stroke| match stroke {
+            Stroke::Line { from, to } => Stroke::Line {
+                from: pt(from),
+                to: pt(to),
+            },
+            Stroke::Arc {
+                center,
+                radius,
+                start,
+                end,
+            } => Stroke::Arc {
+                center: pt(center),
+                radius: (*radius as f64 / 100.0) * width.min(height),
+                start: *start as f64,
+                end: *end as f64,
+            },
+            Stroke::Quadratic { from, ctrl, to } => Stroke::Quadratic {
+                from: pt(from),
+                ctrl: pt(ctrl),
+                to: pt(to),
+            },
+            Stroke::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => Stroke::Cubic {
+                from: pt(from),
+                ctrl1: pt(ctrl1),
+                ctrl2: pt(ctrl2),
+                to: pt(to),
+            },
+            Stroke::Polyline { points } => Stroke::Polyline {
+                points: points.iter().map(pt).collect(),
+            },
+        })
+        .collect()
+}
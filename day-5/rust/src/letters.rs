@@ -1,76 +1,24 @@
-use crate::types::{Pt, Stroke, Glyph};
+use crate::types::{Alternate, Anchor, Font, FontMetrics, Glyph, Ligature, Pt, Stroke};
+use glyphs_macro::glyphs;
 use std::collections::HashMap;
 
-macro_rules! glyphs {
-    (
-        $(
-            $ch:literal {
-                $($body:tt)*
-            }
-        )*
-    ) => {{
-        let mut glyphs = HashMap::new();
-        $(
-            let (strokes, width_restriction) = glyphs!(@parse_strokes $($body)*);
-            let glyph = Glyph {
-                ch: $ch,
-                strokes,
-                width_restriction,
-            };
-            glyphs.insert(glyph.ch, glyph);
-        )*
-        glyphs
-    }};
-
-    // top level recursive parsing
-    (@parse_strokes $($tt:tt)*) => {{
-        let mut strokes_vec: Vec<Stroke> = Vec::new();
-        let mut width_restriction: Option<u8> = None;
-        glyphs!(@strokes strokes_vec, width_restriction, $($tt)*);
-        (strokes_vec, width_restriction)
-    }};
-
-    // Base case: no more strokes
-    (@strokes $out:ident, $width:ident, ) => {};
-
-    // lines grammar
-    (@strokes $out:ident, $width:ident,
-        line ($x1:expr, $y1:expr) -> ($x2:expr, $y2:expr)
-        $($rest:tt)*
-    ) => {{
-        $out.push(Stroke::Line {
-            from: Pt($x1, $y1),
-            to: Pt($x2, $y2),
-        });
-        glyphs!(@strokes $out, $width, $($rest)*);
-    }};
-
-    // arcs grammar
-    (@strokes $out:ident, $width:ident,
-        arc ($x:expr, $y:expr) radius ($r:expr) from ($start:expr) -> ($end:expr)
-        $($rest:tt)*
-    ) => {{
-        $out.push(Stroke::Arc {
-            center: Pt($x, $y),
-            radius: $r,
-            start: $start,
-            end: $end,
-        });
-        glyphs!(@strokes $out, $width, $($rest)*);
-    }};
-
-    // restrict_width grammar
-    (@strokes $out:ident, $width:ident,
-        restrict_width ($w:expr)
-        $($rest:tt)*
-    ) => {{
-        $width = Some($w as u8);
-        glyphs!(@strokes $out, $width, $($rest)*);
-    }};
+pub fn define_glyphs() -> HashMap<char, Glyph> {
+    define_font().glyphs
 }
 
-pub fn define_glyphs() -> HashMap<char, Glyph> {
-    let glyphs = glyphs! {
+pub fn define_font() -> Font {
+    glyphs! {
+        // Lowercase and digits fill the 0-100 box, capitals and the stems of
+        // b, d, h, k and l reach 140 and descenders drop to -50
+        metrics {
+            baseline (0)
+            x_height (100)
+            cap_height (140)
+            ascender (140)
+            descender (-50)
+            line_gap (30)
+        }
+
         // Space
         ' ' {
             // Empty glyph
@@ -107,6 +55,7 @@ pub fn define_glyphs() -> HashMap<char, Glyph> {
             line (0, 0) -> (0, 100)
             arc (0, 50) radius (50) from (0) -> (90)
             restrict_width (10)
+            anchor exit (50, 50)
         }
 
         // Letter a - circle with a vertical line on the right
@@ -119,12 +68,12 @@ pub fn define_glyphs() -> HashMap<char, Glyph> {
         'y' {
             line (0, 100) -> (50, 50)
             line (50, 50) -> (100, 100)
-            line (50, 50) -> (50, 0)
+            line (50, 50) -> (50, -50)
         }
 
         // Letter b
         'b' {
-            line (0, 0) -> (0, 100)
+            line (0, 0) -> (0, 140)
             arc (50, 25) radius (50) from (270) -> (90)
             arc (50, 75) radius (50) from (270) -> (90)
         }
@@ -137,7 +86,7 @@ pub fn define_glyphs() -> HashMap<char, Glyph> {
 
         // Letter d
         'd' {
-            line (100, 0) -> (100, 100)
+            line (100, 0) -> (100, 140)
             arc (50, 50) radius (50) from (90) -> (270)
         }
 
@@ -151,7 +100,7 @@ pub fn define_glyphs() -> HashMap<char, Glyph> {
 
         // Letter h
         'h' {
-            line (0, 0) -> (0, 100)
+            line (0, 0) -> (0, 140)
             line (100, 0) -> (100, 100)
             line (0, 50) -> (100, 50)
         }
@@ -165,15 +114,30 @@ pub fn define_glyphs() -> HashMap<char, Glyph> {
 
         // Letter j
         'j' {
-            line (75, 0) -> (75, 75)
-            arc (50, 25) radius (25) from (0) -> (180)
+            line (75, -50) -> (75, 75)
+            arc (50, -25) radius (25) from (0) -> (180)
             arc (75, 100) radius (5) from (0) -> (360)
             restrict_width (75)
+            anchor top (75, 100)
+        }
+
+        // Dotless i, used under accents
+        'ı' {
+            line (50, 0) -> (50, 100)
+            restrict_width (30)
+        }
+
+        // Dotless j, used under accents
+        'ȷ' {
+            line (75, -50) -> (75, 75)
+            arc (50, -25) radius (25) from (0) -> (180)
+            restrict_width (75)
+            anchor top (75, 100)
         }
 
         // Letter k
         'k' {
-            line (0, 0) -> (0, 100)
+            line (0, 0) -> (0, 140)
             line (0, 50) -> (100, 100)
             line (0, 50) -> (100, 0)
             restrict_width (80)
@@ -181,7 +145,7 @@ pub fn define_glyphs() -> HashMap<char, Glyph> {
 
         // Letter l
         'l' {
-            line (50, 0) -> (50, 100)
+            line (50, 0) -> (50, 140)
             restrict_width (30)
         }
 
@@ -198,11 +162,13 @@ pub fn define_glyphs() -> HashMap<char, Glyph> {
         'o' {
             arc (50, 50) radius (50) from (0) -> (360)
             restrict_width (90)
+            anchor above_right (85, 85)
+            anchor exit (100, 50)
         }
 
         // Letter p
         'p' {
-            line (0, 0) -> (0, 100)
+            line (0, -50) -> (0, 100)
             arc (50, 75) radius (50) from (270) -> (90)
             restrict_width (80)
         }
@@ -210,13 +176,14 @@ pub fn define_glyphs() -> HashMap<char, Glyph> {
         // Letter q
         'q' {
             arc (50, 50) radius (50) from (0) -> (360)
-            line (70, 30) -> (100, 0)
+            line (70, 30) -> (100, -50)
         }
 
-        // Letter s
+        // Letter s - three curves forming the spine
         's' {
-            arc (50, 75) radius (25) from (0) -> (270)
-            arc (50, 25) radius (25) from (180) -> (90)
+            cubic (90, 90) via (65, 100) (10, 100) -> (10, 72)
+            cubic (10, 72) via (10, 50) (90, 50) -> (90, 28)
+            cubic (90, 28) via (90, 0) (35, 0) -> (10, 10)
             restrict_width (70)
         }
 
@@ -231,6 +198,7 @@ pub fn define_glyphs() -> HashMap<char, Glyph> {
         'v' {
             line (0, 100) -> (50, 0)
             line (50, 0) -> (100, 100)
+            anchor exit (100, 100)
         }
 
         // Letter w
@@ -239,6 +207,7 @@ pub fn define_glyphs() -> HashMap<char, Glyph> {
             line (25, 0) -> (50, 50)
             line (50, 50) -> (75, 0)
             line (75, 0) -> (100, 100)
+            anchor exit (100, 100)
         }
 
         // Letter x
@@ -267,13 +236,612 @@ pub fn define_glyphs() -> HashMap<char, Glyph> {
             restrict_width (30)
         }
 
-        // Number 2
+        // Number 2 - curved neck sweeping into the base
         '2' {
-            arc (50, 75) radius (50) from (0) -> (270)
-            line (50, 75) -> (0, 0)
+            cubic (5, 75) via (10, 100) (90, 100) -> (90, 70)
+            cubic (90, 70) via (90, 45) (30, 30) -> (0, 0)
+            line (0, 0) -> (100, 0)
+            restrict_width (80)
+        }
+
+        // Number 3 - two bowls opening to the left
+        '3' {
+            arc (50, 75) radius (25) from (180) -> (-90)
+            arc (50, 25) radius (25) from (90) -> (-180)
+            restrict_width (80)
+        }
+
+        // Number 4
+        '4' {
+            line (75, 0) -> (75, 100)
+            line (75, 100) -> (0, 25)
+            line (0, 25) -> (100, 25)
+            restrict_width (80)
+        }
+
+        // Number 5
+        '5' {
+            line (100, 100) -> (0, 100)
+            line (0, 100) -> (0, 50)
+            line (0, 50) -> (50, 50)
+            arc (50, 25) radius (25) from (90) -> (-180)
+            restrict_width (80)
+        }
+
+        // Number 6 - circle with a diagonal tail
+        '6' {
+            arc (50, 30) radius (30) from (0) -> (360)
+            line (20, 30) -> (60, 100)
+            restrict_width (80)
+        }
+
+        // Number 7
+        '7' {
+            line (0, 100) -> (100, 100)
+            line (100, 100) -> (25, 0)
+            restrict_width (80)
+        }
+
+        // Number 8 - two stacked circles
+        '8' {
+            arc (50, 75) radius (25) from (0) -> (360)
+            arc (50, 25) radius (25) from (0) -> (360)
+            restrict_width (70)
+        }
+
+        // Number 9 - circle with a diagonal tail
+        '9' {
+            arc (50, 70) radius (30) from (0) -> (360)
+            line (80, 70) -> (40, 0)
+            restrict_width (80)
+        }
+
+        // Capital A
+        'A' {
+            line (0, 0) -> (50, 140)
+            line (50, 140) -> (100, 0)
+            line (25, 70) -> (75, 70)
+        }
+
+        // Capital B - stem with two bowls
+        'B' {
+            line (0, 0) -> (0, 140)
+            line (0, 140) -> (50, 140)
+            arc (50, 105) radius (35) from (90) -> (-90)
+            line (0, 70) -> (50, 70)
+            arc (50, 35) radius (35) from (90) -> (-90)
+            line (50, 0) -> (0, 0)
+            restrict_width (80)
+        }
+
+        // Capital C
+        'C' {
+            arc (50, 90) radius (50) from (30) -> (180)
+            line (0, 90) -> (0, 50)
+            arc (50, 50) radius (50) from (180) -> (330)
+            restrict_width (80)
+        }
+
+        // Capital D
+        'D' {
+            line (0, 0) -> (0, 140)
+            line (0, 140) -> (30, 140)
+            arc (30, 70) radius (70) from (90) -> (-90)
+            line (30, 0) -> (0, 0)
+        }
+
+        // Capital E
+        'E' {
+            line (100, 140) -> (0, 140)
+            line (0, 140) -> (0, 0)
+            line (0, 0) -> (100, 0)
+            line (0, 70) -> (75, 70)
+            restrict_width (80)
+        }
+
+        // Capital F
+        'F' {
+            line (100, 140) -> (0, 140)
+            line (0, 140) -> (0, 0)
+            line (0, 70) -> (75, 70)
+            restrict_width (80)
+        }
+
+        // Capital G - open circle with an inward bar
+        'G' {
+            arc (50, 90) radius (50) from (30) -> (180)
+            line (0, 90) -> (0, 50)
+            arc (50, 50) radius (50) from (180) -> (360)
+            line (100, 50) -> (100, 70)
+            line (100, 70) -> (60, 70)
+        }
+
+        // Capital H
+        'H' {
+            line (0, 0) -> (0, 140)
+            line (100, 0) -> (100, 140)
+            line (0, 70) -> (100, 70)
+        }
+
+        // Capital I
+        'I' {
+            line (50, 0) -> (50, 140)
+            line (25, 140) -> (75, 140)
+            line (25, 0) -> (75, 0)
+            restrict_width (50)
+        }
+
+        // Capital J
+        'J' {
+            line (75, 140) -> (75, 25)
+            arc (50, 25) radius (25) from (0) -> (-180)
+            restrict_width (75)
+        }
+
+        // Capital K
+        'K' {
+            line (0, 0) -> (0, 140)
+            line (0, 70) -> (100, 140)
+            line (0, 70) -> (100, 0)
+            restrict_width (80)
+        }
+
+        // Capital L
+        'L' {
+            line (0, 140) -> (0, 0)
+            line (0, 0) -> (100, 0)
+            restrict_width (80)
+        }
+
+        // Capital M
+        'M' {
+            line (0, 0) -> (0, 140)
+            line (0, 140) -> (50, 70)
+            line (50, 70) -> (100, 140)
+            line (100, 140) -> (100, 0)
+        }
+
+        // Capital N
+        'N' {
+            line (0, 0) -> (0, 140)
+            line (0, 140) -> (100, 0)
+            line (100, 0) -> (100, 140)
+        }
+
+        // Capital O
+        'O' {
+            arc (50, 90) radius (50) from (0) -> (180)
+            line (0, 90) -> (0, 50)
+            arc (50, 50) radius (50) from (180) -> (360)
+            line (100, 50) -> (100, 90)
+        }
+
+        // Capital P
+        'P' {
+            line (0, 0) -> (0, 140)
+            line (0, 140) -> (50, 140)
+            arc (50, 105) radius (35) from (90) -> (-90)
+            line (50, 70) -> (0, 70)
+            restrict_width (80)
+        }
+
+        // Capital Q - circle with a tail crossing the bottom right
+        'Q' {
+            arc (50, 90) radius (50) from (0) -> (180)
+            line (0, 90) -> (0, 50)
+            arc (50, 50) radius (50) from (180) -> (360)
+            line (100, 50) -> (100, 90)
+            line (60, 40) -> (100, 0)
+        }
+
+        // Capital R
+        'R' {
+            line (0, 0) -> (0, 140)
+            line (0, 140) -> (50, 140)
+            arc (50, 105) radius (35) from (90) -> (-90)
+            line (50, 70) -> (0, 70)
+            line (50, 70) -> (100, 0)
+            restrict_width (80)
+        }
+
+        // Capital S
+        'S' {
+            arc (50, 105) radius (35) from (0) -> (270)
+            arc (50, 35) radius (35) from (90) -> (-180)
+            restrict_width (70)
+        }
+
+        // Capital T
+        'T' {
+            line (0, 140) -> (100, 140)
+            line (50, 140) -> (50, 0)
+        }
+
+        // Capital U
+        'U' {
+            line (0, 140) -> (0, 50)
+            arc (50, 50) radius (50) from (180) -> (360)
+            line (100, 50) -> (100, 140)
+        }
+
+        // Capital V
+        'V' {
+            line (0, 140) -> (50, 0)
+            line (50, 0) -> (100, 140)
+        }
+
+        // Capital W
+        'W' {
+            line (0, 140) -> (25, 0)
+            line (25, 0) -> (50, 70)
+            line (50, 70) -> (75, 0)
+            line (75, 0) -> (100, 140)
+        }
+
+        // Capital X
+        'X' {
+            line (0, 0) -> (100, 140)
+            line (0, 140) -> (100, 0)
+        }
+
+        // Capital Y
+        'Y' {
+            line (0, 140) -> (50, 70)
+            line (100, 140) -> (50, 70)
+            line (50, 70) -> (50, 0)
+        }
+
+        // Capital Z
+        'Z' {
+            line (0, 140) -> (100, 140)
+            line (100, 140) -> (0, 0)
+            line (0, 0) -> (100, 0)
             restrict_width (80)
         }
-    };
 
-    glyphs
-}
\ No newline at end of file
+        // Exclamation mark
+        '!' {
+            line (50, 140) -> (50, 30)
+            arc (50, 5) radius (5) from (0) -> (360)
+            restrict_width (10)
+        }
+
+        // Double quote
+        '"' {
+            line (35, 140) -> (35, 105)
+            line (65, 140) -> (65, 105)
+            restrict_width (40)
+        }
+
+        // Hash
+        '#' {
+            line (30, 10) -> (40, 90)
+            line (60, 10) -> (70, 90)
+            line (10, 35) -> (90, 35)
+            line (10, 65) -> (90, 65)
+        }
+
+        // Dollar - s with a vertical bar
+        '$' {
+            arc (50, 100) radius (30) from (0) -> (270)
+            arc (50, 40) radius (30) from (90) -> (-180)
+            line (50, 140) -> (50, 0)
+            restrict_width (70)
+        }
+
+        // Percent
+        '%' {
+            line (0, 0) -> (100, 140)
+            arc (20, 115) radius (15) from (0) -> (360)
+            arc (80, 25) radius (15) from (0) -> (360)
+        }
+
+        // Ampersand - small loop on top, bowl at the bottom
+        '&' {
+            arc (40, 110) radius (25) from (-60) -> (240)
+            line (27, 88) -> (100, 0)
+            line (52, 88) -> (5, 30)
+            arc (30, 30) radius (25) from (180) -> (300)
+            line (42, 8) -> (90, 45)
+        }
+
+        // Apostrophe
+        '\'' {
+            line (50, 140) -> (50, 105)
+            restrict_width (10)
+        }
+
+        // Left parenthesis
+        '(' {
+            arc (90, 70) radius (90) from (130) -> (230)
+            restrict_width (40)
+        }
+
+        // Right parenthesis
+        ')' {
+            arc (10, 70) radius (90) from (50) -> (-50)
+            restrict_width (40)
+        }
+
+        // Asterisk
+        '*' {
+            line (50, 75) -> (50, 25)
+            line (28, 62) -> (72, 38)
+            line (28, 38) -> (72, 62)
+            restrict_width (70)
+        }
+
+        // Plus
+        '+' {
+            line (50, 80) -> (50, 20)
+            line (20, 50) -> (80, 50)
+            restrict_width (70)
+        }
+
+        // Comma
+        ',' {
+            line (55, 15) -> (35, -20)
+            restrict_width (10)
+        }
+
+        // Hyphen
+        '-' {
+            line (20, 50) -> (80, 50)
+            restrict_width (60)
+        }
+
+        // Period
+        '.' {
+            arc (50, 5) radius (5) from (0) -> (360)
+            restrict_width (10)
+        }
+
+        // Slash
+        '/' {
+            line (0, 0) -> (100, 140)
+            restrict_width (60)
+        }
+
+        // Colon
+        ':' {
+            arc (50, 70) radius (5) from (0) -> (360)
+            arc (50, 20) radius (5) from (0) -> (360)
+            restrict_width (10)
+        }
+
+        // Semicolon
+        ';' {
+            arc (50, 70) radius (5) from (0) -> (360)
+            line (55, 15) -> (35, -20)
+            restrict_width (10)
+        }
+
+        // Less than
+        '<' {
+            line (100, 100) -> (0, 50)
+            line (0, 50) -> (100, 0)
+            restrict_width (80)
+        }
+
+        // Equals
+        '=' {
+            line (10, 65) -> (90, 65)
+            line (10, 35) -> (90, 35)
+            restrict_width (80)
+        }
+
+        // Greater than
+        '>' {
+            line (0, 100) -> (100, 50)
+            line (100, 50) -> (0, 0)
+            restrict_width (80)
+        }
+
+        // Question mark - hook, stem and dot
+        '?' {
+            arc (50, 105) radius (35) from (180) -> (-90)
+            line (50, 70) -> (50, 30)
+            arc (50, 5) radius (5) from (0) -> (360)
+            restrict_width (70)
+        }
+
+        // At sign - open outer circle around an a
+        '@' {
+            arc (50, 50) radius (50) from (0) -> (330)
+            arc (50, 50) radius (20) from (0) -> (360)
+            line (70, 70) -> (70, 35)
+            arc (80, 35) radius (10) from (180) -> (360)
+            line (90, 35) -> (100, 50)
+        }
+
+        // Left square bracket
+        '[' {
+            line (60, 140) -> (30, 140)
+            line (30, 140) -> (30, -20)
+            line (30, -20) -> (60, -20)
+            restrict_width (40)
+        }
+
+        // Backslash
+        '\\' {
+            line (0, 140) -> (100, 0)
+            restrict_width (60)
+        }
+
+        // Right square bracket
+        ']' {
+            line (40, 140) -> (70, 140)
+            line (70, 140) -> (70, -20)
+            line (70, -20) -> (40, -20)
+            restrict_width (40)
+        }
+
+        // Caret
+        '^' {
+            line (20, 60) -> (50, 100)
+            line (50, 100) -> (80, 60)
+            restrict_width (70)
+        }
+
+        // Underscore
+        '_' {
+            line (0, 0) -> (100, 0)
+        }
+
+        // Backtick
+        '`' {
+            line (40, 140) -> (60, 120)
+            restrict_width (10)
+        }
+
+        // Left curly brace
+        '{' {
+            line (70, 140) -> (50, 130)
+            line (50, 130) -> (50, 70)
+            line (50, 70) -> (30, 60)
+            line (30, 60) -> (50, 50)
+            line (50, 50) -> (50, -10)
+            line (50, -10) -> (70, -20)
+            restrict_width (40)
+        }
+
+        // Vertical bar
+        '|' {
+            line (50, -20) -> (50, 140)
+            restrict_width (30)
+        }
+
+        // Right curly brace
+        '}' {
+            line (30, 140) -> (50, 130)
+            line (50, 130) -> (50, 70)
+            line (50, 70) -> (70, 60)
+            line (70, 60) -> (50, 50)
+            line (50, 50) -> (50, -10)
+            line (50, -10) -> (30, -20)
+            restrict_width (40)
+        }
+
+        // Tilde - two half circles
+        '~' {
+            arc (30, 50) radius (20) from (180) -> (0)
+            arc (70, 50) radius (20) from (180) -> (360)
+            restrict_width (80)
+        }
+
+        // Combining marks, drawn above (or below) their anchor. Accented
+        // letters are built from these, see marks.rs
+
+        // Combining grave accent
+        '\u{300}' {
+            line (35, 40) -> (55, 15)
+            anchor top (50, 0)
+        }
+
+        // Combining acute accent
+        '\u{301}' {
+            line (45, 15) -> (65, 40)
+            anchor top (50, 0)
+        }
+
+        // Combining circumflex accent
+        '\u{302}' {
+            poly (30, 15) -> (50, 35) -> (70, 15)
+            anchor top (50, 0)
+        }
+
+        // Combining tilde
+        '\u{303}' {
+            cubic (25, 20) via (40, 40) (60, 5) -> (75, 25)
+            anchor top (50, 0)
+        }
+
+        // Combining macron
+        '\u{304}' {
+            line (25, 20) -> (75, 20)
+            anchor top (50, 0)
+        }
+
+        // Combining dot above
+        '\u{307}' {
+            arc (50, 20) radius (5) from (0) -> (360)
+            anchor top (50, 0)
+        }
+
+        // Combining diaeresis
+        '\u{308}' {
+            arc (30, 20) radius (5) from (0) -> (360)
+            arc (70, 20) radius (5) from (0) -> (360)
+            anchor top (50, 0)
+        }
+
+        // Combining ring above
+        '\u{30a}' {
+            arc (50, 27) radius (12) from (0) -> (360)
+            anchor top (50, 0)
+        }
+
+        // Combining caron
+        '\u{30c}' {
+            poly (30, 35) -> (50, 15) -> (70, 35)
+            anchor top (50, 0)
+        }
+
+        // Combining horn, off the top right of o and u
+        '\u{31b}' {
+            quad (0, 0) via (15, 0) -> (15, 20)
+            anchor above_right (0, 0)
+        }
+
+        // Combining cedilla, hangs from the bottom
+        '\u{327}' {
+            line (50, 0) -> (50, -15)
+            quad (50, -15) via (75, -20) -> (40, -40)
+            anchor bottom (50, 0)
+        }
+
+        // Ligatures, drawn in place of the pairs and as wide as both
+
+        // ff - both stems share one crossbar
+        ligature 'f' 'f' {
+            line (25, 0) -> (25, 100)
+            line (125, 0) -> (125, 100)
+            line (25, 100) -> (175, 100)
+            line (25, 50) -> (175, 50)
+            restrict_width (75)
+        }
+
+        // fi - the top of the f runs into the i, which loses its dot
+        ligature 'f' 'i' {
+            line (25, 0) -> (25, 100)
+            line (25, 100) -> (150, 100)
+            line (150, 100) -> (150, 0)
+            line (25, 50) -> (75, 50)
+            restrict_width (30)
+        }
+
+        // Alternates
+
+        // r before n and m - more room so rn doesn't read as m
+        alternate 'r' before ('n', 'm') {
+            line (0, 0) -> (0, 100)
+            arc (0, 50) radius (50) from (0) -> (90)
+            restrict_width (60)
+        }
+
+        // t with a hooked foot, picked at random
+        alternate 't' {
+            line (50, 100) -> (50, 25)
+            arc (75, 25) radius (25) from (180) -> (270)
+            line (25, 100) -> (75, 100)
+            restrict_width (70)
+        }
+
+        // l with a hooked foot, picked at random
+        alternate 'l' {
+            line (50, 140) -> (50, 25)
+            arc (75, 25) radius (25) from (180) -> (270)
+            restrict_width (30)
+        }
+    }
+}
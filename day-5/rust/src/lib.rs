@@ -0,0 +1,17 @@
+pub mod clip;
+pub mod cursive;
+pub mod drawing;
+pub mod font_data;
+pub mod geometry;
+pub mod glyph_file;
+pub mod kerning;
+pub mod layout;
+pub mod letters;
+pub mod marks;
+pub mod optimize;
+pub mod path_text;
+pub mod renderers;
+pub mod sketch;
+pub mod substitution;
+pub mod transform;
+pub mod types;
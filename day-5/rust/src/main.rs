@@ -1,7 +1,531 @@
-mod letters;
-pub mod types;
+use std::error::Error;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
-fn main() {
-    let glyphs = letters::define_glyphs();
-    println!("{:?}", glyphs);
-}
\ No newline at end of file
+use clap::{Parser, Subcommand, ValueEnum};
+use day_5::clip::ClipReport;
+use day_5::drawing::{Drawing, DrawingOptions};
+use day_5::font_data::FontDocument;
+use day_5::glyph_file;
+use day_5::kerning::{KerningConfig, KerningTable};
+use day_5::layout::{Align, LayoutResult, TextLayout};
+use day_5::letters;
+use day_5::optimize::{optimize, OptimizeOptions};
+use day_5::renderers::{
+    GcodeConfig, GcodeDrawing, HpglConfig, HpglDrawing, LineCap, LineJoin, PngConfig, PngDrawing,
+    PostScriptDrawing, SvgDrawing,
+};
+use day_5::sketch::{sketch_layout, SketchConfig};
+use day_5::types::Font;
+
+#[derive(Parser)]
+#[command(name = "day-5", about = "Render text with the genurary stroke font")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Render text to a file or stdout
+    Render(Box<RenderArgs>),
+    /// Write the genurary logo in every format, like generate_postscript.rb
+    Demo,
+}
+
+#[derive(clap::Args)]
+struct RenderArgs {
+    /// Text to render, \n starts a new line
+    #[arg(long)]
+    text: String,
+
+    /// Output format, guessed from --out when not given
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+
+    /// Output file, stdout when not given or `-`
+    #[arg(long)]
+    out: Option<PathBuf>,
+
+    /// Glyph size, 1 is 40 output units, the same size on paper for plotters
+    #[arg(long)]
+    scale: Option<f64>,
+
+    /// Multiplier for the space between glyphs
+    #[arg(long, default_value_t = 1.0)]
+    spacing: f64,
+
+    /// Left edge of the text, 100 points in or the same in mm for plotters
+    #[arg(long)]
+    x: Option<f64>,
+
+    /// Baseline of the first line, 400 points up or the same in mm for plotters
+    #[arg(long)]
+    y: Option<f64>,
+
+    /// Wrap lines wider than this
+    #[arg(long)]
+    max_width: Option<f64>,
+
+    #[arg(long, value_enum, default_value_t = AlignArg::Left)]
+    align: AlignArg,
+
+    /// Baseline to baseline distance, from the font metrics by default
+    #[arg(long)]
+    line_height: Option<f64>,
+
+    /// Pick among the font's glyph variants and shake sketched strokes, the same seed gives the same drawing
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Kern pairs from the glyph shapes, the font's own kerning is always used
+    #[arg(long)]
+    kern: bool,
+
+    /// Join the letters of every word into one pen stroke
+    #[arg(long)]
+    cursive: bool,
+
+    /// Draw strokes like by hand, how rough from 0 up, 1 looks inked
+    #[arg(long)]
+    sketch: Option<f64>,
+
+    /// Draw every sketched stroke twice
+    #[arg(long, requires = "sketch")]
+    double_stroke: bool,
+
+    #[arg(long, default_value_t = 4.0)]
+    line_width: f64,
+
+    /// Stroke color as #rrggbb or r,g,b in the 0-1 range
+    #[arg(long, default_value = "0.2,0.2,0.2", value_parser = parse_color)]
+    color: [f64; 3],
+
+    /// Page size as WIDTHxHEIGHT in output units (points, mm for G-code) or letter, a4, a3
+    #[arg(long, default_value = "letter", value_parser = parse_page)]
+    page: Page,
+
+    /// Background color, an SVG color for SVG and white, black, none, #rrggbb or r,g,b for PNG
+    #[arg(long, default_value = "white")]
+    background: String,
+
+    /// PNG resolution, 72 gives one pixel per point
+    #[arg(long, default_value_t = 96.0)]
+    dpi: f64,
+
+    /// PNG line ends
+    #[arg(long, value_enum, default_value_t = LineCapArg::Round)]
+    line_cap: LineCapArg,
+
+    /// PNG corners
+    #[arg(long, value_enum, default_value_t = LineJoinArg::Round)]
+    line_join: LineJoinArg,
+
+    /// Font file (.glyphs, .json or .toml), the built in font when not given
+    #[arg(long)]
+    font: Option<PathBuf>,
+
+    /// Reorder strokes to cut pen-up travel, for G-code and HP-GL
+    #[arg(long)]
+    optimize: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Svg,
+    Ps,
+    Gcode,
+    Hpgl,
+    Png,
+}
+
+impl Format {
+    // Plotters measure in mm, everything else in points
+    fn mm_per_unit(self) -> f64 {
+        match self {
+            Format::Gcode | Format::Hpgl => 1.0,
+            Format::Svg | Format::Ps | Format::Png => MM_PER_POINT,
+        }
+    }
+
+    fn from_path(path: &Path) -> Option<Format> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "svg" => Some(Format::Svg),
+            "ps" | "eps" => Some(Format::Ps),
+            "gcode" | "nc" | "gc" => Some(Format::Gcode),
+            "hpgl" | "plt" => Some(Format::Hpgl),
+            "png" => Some(Format::Png),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum AlignArg {
+    Left,
+    Center,
+    Right,
+    Justify,
+}
+
+impl From<AlignArg> for Align {
+    fn from(align: AlignArg) -> Self {
+        match align {
+            AlignArg::Left => Align::Left,
+            AlignArg::Center => Align::Center,
+            AlignArg::Right => Align::Right,
+            AlignArg::Justify => Align::Justify,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LineCapArg {
+    Butt,
+    Round,
+    Square,
+}
+
+impl From<LineCapArg> for LineCap {
+    fn from(cap: LineCapArg) -> Self {
+        match cap {
+            LineCapArg::Butt => LineCap::Butt,
+            LineCapArg::Round => LineCap::Round,
+            LineCapArg::Square => LineCap::Square,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LineJoinArg {
+    Miter,
+    Round,
+    Bevel,
+}
+
+impl From<LineJoinArg> for LineJoin {
+    fn from(join: LineJoinArg) -> Self {
+        match join {
+            LineJoinArg::Miter => LineJoin::Miter,
+            LineJoinArg::Round => LineJoin::Round,
+            LineJoinArg::Bevel => LineJoin::Bevel,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Page {
+    // Paper sizes in mm, converted to the output's units
+    Paper(f64, f64),
+    Custom(f64, f64),
+}
+
+impl Page {
+    // Width and height for an output measured in `mm_per_unit`, paper sizes
+    // are rounded so letter comes out as 612x792 points
+    fn size(self, mm_per_unit: f64) -> (f64, f64) {
+        let convert = |mm: f64| (mm / mm_per_unit * 1000.0).round() / 1000.0;
+        match self {
+            Page::Paper(w, h) => (convert(w), convert(h)),
+            Page::Custom(w, h) => (w, h),
+        }
+    }
+}
+
+const MM_PER_POINT: f64 = 25.4 / 72.0;
+
+// Where the text goes and how big without --x, --y and --scale, in points.
+// Plotters get the same place and size on the paper.
+const DEFAULT_X: f64 = 100.0;
+const DEFAULT_Y: f64 = 400.0;
+const DEFAULT_SCALE: f64 = 1.0;
+
+fn parse_page(value: &str) -> Result<Page, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "letter" => Ok(Page::Paper(215.9, 279.4)),
+        "a4" => Ok(Page::Paper(210.0, 297.0)),
+        "a3" => Ok(Page::Paper(297.0, 420.0)),
+        size => {
+            let (w, h) = size
+                .split_once('x')
+                .ok_or_else(|| format!("expected WIDTHxHEIGHT, letter, a4 or a3, got {value:?}"))?;
+            let parse = |n: &str| {
+                n.trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|n| *n > 0.0)
+                    .ok_or_else(|| format!("invalid page dimension {n:?}"))
+            };
+            Ok(Page::Custom(parse(w)?, parse(h)?))
+        }
+    }
+}
+
+fn parse_color(value: &str) -> Result<[f64; 3], String> {
+    if let Some(hex) = value.strip_prefix('#') {
+        // Byte slicing below needs one byte per digit
+        if hex.len() != 6 || !hex.is_ascii() {
+            return Err(format!("expected #rrggbb, got {value:?}"));
+        }
+        let channel = |i: usize| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map(|c| c as f64 / 255.0)
+                .map_err(|_| format!("invalid hex color {value:?}"))
+        };
+        return Ok([channel(0)?, channel(2)?, channel(4)?]);
+    }
+
+    let parts: Vec<f64> = value
+        .split(',')
+        .map(|c| c.trim().parse::<f64>())
+        .collect::<Result<_, _>>()
+        .map_err(|_| format!("expected #rrggbb or r,g,b, got {value:?}"))?;
+    match parts[..] {
+        [r, g, b] if parts.iter().all(|c| (0.0..=1.0).contains(c)) => Ok([r, g, b]),
+        _ => Err(format!(
+            "expected three components in the 0-1 range, got {value:?}"
+        )),
+    }
+}
+
+// PNG has no named colors, so only the common ones are understood
+fn parse_background(value: &str) -> Result<Option<[f64; 3]>, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "none" | "transparent" => Ok(None),
+        "white" => Ok(Some([1.0, 1.0, 1.0])),
+        "black" => Ok(Some([0.0, 0.0, 0.0])),
+        _ => parse_color(value).map(Some),
+    }
+}
+
+fn load_font(path: &Path) -> Result<Font, Box<dyn Error>> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("glyphs") => Ok(glyph_file::load_font(path)?),
+        _ => Ok(FontDocument::load(path)?.to_font()),
+    }
+}
+
+fn render(args: RenderArgs) -> Result<(), Box<dyn Error>> {
+    let format = args
+        .format
+        .or_else(|| args.out.as_deref().and_then(Format::from_path))
+        .unwrap_or(Format::Svg);
+    let font = match &args.font {
+        Some(path) => load_font(path)?,
+        None => letters::define_font(),
+    };
+
+    let per_point = MM_PER_POINT / format.mm_per_unit();
+    let mut layout = TextLayout::new(
+        args.x.unwrap_or(DEFAULT_X * per_point),
+        args.y.unwrap_or(DEFAULT_Y * per_point),
+        args.scale.unwrap_or(DEFAULT_SCALE * per_point),
+        args.spacing,
+    )
+    .align(args.align.into());
+    if let Some(max_width) = args.max_width {
+        layout = layout.max_width(max_width);
+    }
+    if let Some(line_height) = args.line_height {
+        layout = layout.line_height(line_height);
+    }
+    if let Some(seed) = args.seed {
+        layout = layout.seed(seed);
+    }
+    let kerning = if args.kern {
+        let config = KerningConfig {
+            spacing: KerningConfig::default().spacing * args.spacing,
+            ..Default::default()
+        };
+        KerningTable::auto(&font, &config)
+    } else {
+        KerningTable::from_font(&font)
+    };
+    if !kerning.is_empty() {
+        layout = layout.kerning(kerning);
+    }
+    layout = layout.cursive(args.cursive);
+    // Let people type \n on the command line
+    let text = args.text.replace("\\n", "\n");
+    let mut laid_out = layout.layout(&font, &text);
+    for missing in &laid_out.missing {
+        eprintln!(
+            "warning: no glyph for {:?} at {}",
+            missing.ch, missing.index
+        );
+    }
+    if let Some(roughness) = args.sketch {
+        let config = SketchConfig {
+            roughness,
+            double: args.double_stroke,
+            ..Default::default()
+        }
+        .scaled(per_point);
+        sketch_layout(&mut laid_out, &config, args.seed.unwrap_or(0));
+    }
+
+    let options = DrawingOptions {
+        line_width: args.line_width,
+        color: args.color,
+        ..Default::default()
+    };
+
+    let output = match format {
+        Format::Svg => {
+            let (width, height) = args.page.size(MM_PER_POINT);
+            let mut svg = SvgDrawing::with_page(options, width, height, &args.background);
+            svg.draw_text(&laid_out);
+            svg.render().into_bytes()
+        }
+        Format::Ps => {
+            let (width, height) = args.page.size(MM_PER_POINT);
+            let mut ps = PostScriptDrawing::with_page(options, width, height);
+            ps.draw_text(&laid_out);
+            ps.render().into_bytes()
+        }
+        Format::Gcode => {
+            let (bed_width, bed_height) = args.page.size(1.0);
+            let config = GcodeConfig {
+                bed_width,
+                bed_height,
+                ..Default::default()
+            };
+            let mut gcode = GcodeDrawing::new(options, config);
+            draw_plotter(&mut gcode, &laid_out, args.optimize);
+            warn_clipped(gcode.clip_report(), "the bed");
+            gcode.render().into_bytes()
+        }
+        Format::Hpgl => {
+            let (page_width, page_height) = args.page.size(1.0);
+            let config = HpglConfig {
+                page_width,
+                page_height,
+                ..Default::default()
+            };
+            let mut hpgl = HpglDrawing::new(options, config);
+            draw_plotter(&mut hpgl, &laid_out, args.optimize);
+            warn_clipped(hpgl.clip_report(), "the paper");
+            hpgl.render().into_bytes()
+        }
+        Format::Png => {
+            let (width, height) = args.page.size(MM_PER_POINT);
+            let config = PngConfig {
+                width,
+                height,
+                dpi: args.dpi,
+                background: parse_background(&args.background)?,
+                line_cap: args.line_cap.into(),
+                line_join: args.line_join.into(),
+                ..Default::default()
+            };
+            let mut png = PngDrawing::new(options, config)?;
+            png.draw_text(&laid_out);
+            png.encode_png()?
+        }
+    };
+
+    match args.out.as_deref() {
+        Some(path) if path != Path::new("-") => fs::write(path, output)?,
+        _ => std::io::stdout().write_all(&output)?,
+    }
+    Ok(())
+}
+
+fn warn_clipped(clipped: &ClipReport, edge: &str) {
+    if clipped.is_clipped() {
+        eprintln!(
+            "warning: {} strokes cross the edge of {edge}, {:.1}mm clipped",
+            clipped.split + clipped.removed,
+            clipped.clipped_length()
+        );
+    }
+}
+
+fn draw_plotter(drawing: &mut impl Drawing, text: &LayoutResult, optimized: bool) {
+    if !optimized {
+        drawing.draw_text(text);
+        return;
+    }
+    let strokes: Vec<_> = text.strokes().cloned().collect();
+    let paths = optimize(&strokes, &OptimizeOptions::default());
+    eprintln!(
+        "Pen-up travel: {:.1}mm -> {:.1}mm",
+        paths.travel_before, paths.travel_after
+    );
+    for stroke in paths.strokes() {
+        drawing.draw_stroke(stroke);
+    }
+}
+
+fn demo() -> Result<(), Box<dyn Error>> {
+    let font = letters::define_font();
+
+    let text = TextLayout::new(100.0, 400.0, 1.0, 1.0).layout(&font, "genurary");
+    for missing in &text.missing {
+        eprintln!("no glyph for {:?} at {}", missing.ch, missing.index);
+    }
+
+    let mut ps = PostScriptDrawing::new(DrawingOptions::default());
+    ps.draw_text(&text);
+    fs::write("logo-rust.ps", ps.render())?;
+
+    let mut svg = SvgDrawing::new(DrawingOptions::default());
+    svg.draw_text(&text);
+    fs::write("logo-rust.svg", svg.render())?;
+
+    // Smaller text with wider spacing for the plotter
+    let plotter_text = TextLayout::new(10.0, 50.0, 0.5, 1.5).layout(&font, "genurary");
+    let mut gcode = GcodeDrawing::new(
+        DrawingOptions {
+            line_width: 0.5,
+            ..Default::default()
+        },
+        GcodeConfig {
+            feed_rate: 1500.0,
+            pen_up_position: 80,
+            pen_down_position: 40,
+            origin_x: 20.0,
+            origin_y: 20.0,
+            ..Default::default()
+        },
+    );
+    // Plot in an order that keeps pen-up travel short
+    let strokes: Vec<_> = plotter_text.strokes().cloned().collect();
+    let optimized = optimize(&strokes, &OptimizeOptions::default());
+    println!(
+        "Pen-up travel: {:.1}mm -> {:.1}mm",
+        optimized.travel_before, optimized.travel_after
+    );
+    for stroke in optimized.strokes() {
+        gcode.draw_stroke(stroke);
+    }
+    fs::write("logo-rust.gcode", gcode.render())?;
+
+    // Same paths for HP-GL plotters
+    let mut hpgl = HpglDrawing::new(DrawingOptions::default(), HpglConfig::default());
+    for stroke in optimized.strokes() {
+        hpgl.draw_stroke(stroke);
+    }
+    fs::write("logo-rust.hpgl", hpgl.render())?;
+
+    println!("PostScript, SVG, G-code and HP-GL files generated successfully!");
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Render(args) => render(*args),
+        Command::Demo => demo(),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
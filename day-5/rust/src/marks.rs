@@ -0,0 +1,183 @@
+// Accented characters built from a base glyph and combining marks.
+//
+// A character without a glyph of its own is decomposed (Unicode NFD), so 'é'
+// becomes 'e' followed by U+0301 COMBINING ACUTE ACCENT, and each mark is
+// moved so its anchor lands on the matching anchor of the base. Marks on the
+// same anchor stack outwards. Anchors a glyph doesn't define are taken from
+// the edges of its strokes.
+use unicode_normalization::char::{canonical_combining_class, decompose_canonical};
+
+use crate::geometry::Bounds;
+use crate::layout::place_strokes;
+use crate::types::{Anchor, Font, Glyph, Pt};
+
+// Space between a base and a mark without an explicit anchor, in glyph units
+pub const MARK_GAP: f64 = 15.0;
+
+// Combining classes for marks below and above right of the base
+const BELOW: [u8; 3] = [202, 218, 220];
+const ABOVE_RIGHT: [u8; 2] = [216, 232];
+
+// A base glyph with the marks that go on it
+#[derive(Debug, Clone)]
+pub struct Composed<'a> {
+    pub base: &'a Glyph,
+    pub marks: Vec<&'a Glyph>,
+}
+
+impl<'a> Composed<'a> {
+    pub fn new(base: &'a Glyph) -> Self {
+        Self {
+            base,
+            marks: Vec::new(),
+        }
+    }
+
+    // i and j lose their dot under a mark on top, when the font has the
+    // dotless forms
+    pub fn push_mark(&mut self, font: &'a Font, mark: &'a Glyph) {
+        if mark_anchor(mark) == Anchor::Top {
+            let dotless = match self.base.ch {
+                'i' => font.glyphs.get(&'ı'),
+                'j' => font.glyphs.get(&'ȷ'),
+                _ => None,
+            };
+            if let Some(dotless) = dotless {
+                self.base = dotless;
+            }
+        }
+        self.marks.push(mark);
+    }
+
+    // Offset of every mark in glyph units, in the order of marks
+    pub fn mark_offsets(&self, font: &Font) -> Vec<Pt<f64>> {
+        let mut anchors: Vec<(Anchor, Pt<f64>)> = Vec::new();
+        let mut offsets = Vec::with_capacity(self.marks.len());
+
+        for mark in &self.marks {
+            let anchor = mark_anchor(mark);
+            let target = match anchors.iter().find(|(a, _)| *a == anchor) {
+                Some(&(_, p)) => p,
+                None => base_anchor(font, self.base, anchor),
+            };
+            let point = attach_point(mark, anchor);
+            let offset = Pt(target.0 - point.0, target.1 - point.1);
+            offsets.push(offset);
+
+            // The next mark on this anchor goes past this one, keeping the x
+            let next = match ink_bounds(mark) {
+                Some(b) => Pt(target.0, offset.1 + edge_y(&b, anchor)),
+                None => target,
+            };
+            anchors.retain(|(a, _)| *a != anchor);
+            anchors.push((anchor, next));
+        }
+
+        offsets
+    }
+}
+
+pub fn is_mark(ch: char) -> bool {
+    canonical_combining_class(ch) != 0
+}
+
+// The glyph for a combining mark, None for anything that isn't one
+pub fn mark_glyph(font: &Font, ch: char) -> Option<&Glyph> {
+    if is_mark(ch) {
+        font.glyphs.get(&ch)
+    } else {
+        None
+    }
+}
+
+// A character the font has no glyph for, put together from its canonical
+// decomposition. None when the base or one of the marks is missing.
+pub fn compose(font: &Font, ch: char) -> Option<Composed<'_>> {
+    let mut parts = Vec::new();
+    decompose_canonical(ch, |c| parts.push(c));
+    let (&base, marks) = parts.split_first()?;
+    if marks.is_empty() || is_mark(base) {
+        return None;
+    }
+
+    let mut composed = Composed::new(font.glyphs.get(&base)?);
+    for &mark in marks {
+        composed.push_mark(font, mark_glyph(font, mark)?);
+    }
+    Some(composed)
+}
+
+// Which anchor a mark attaches to, its own anchor if it has one, otherwise
+// from its combining class
+pub fn mark_anchor(mark: &Glyph) -> Anchor {
+    if let Some(&anchor) = mark.anchors.keys().find(|a| !a.is_cursive()) {
+        return anchor;
+    }
+    let class = canonical_combining_class(mark.ch);
+    if BELOW.contains(&class) {
+        Anchor::Bottom
+    } else if ABOVE_RIGHT.contains(&class) {
+        Anchor::AboveRight
+    } else {
+        Anchor::Top
+    }
+}
+
+// Where a mark goes on a base glyph
+pub fn base_anchor(font: &Font, glyph: &Glyph, anchor: Anchor) -> Pt<f64> {
+    if let Some(p) = glyph.anchors.get(&anchor) {
+        return Pt(p.0 as f64, p.1 as f64);
+    }
+    let metrics = &font.metrics;
+    let Some(b) = ink_bounds(glyph) else {
+        // Nothing drawn, use the x-height box
+        return match anchor {
+            Anchor::Top => Pt(50.0, metrics.x_height as f64),
+            Anchor::Bottom => Pt(50.0, metrics.baseline as f64),
+            Anchor::AboveRight => Pt(100.0, metrics.x_height as f64),
+            Anchor::Entry => Pt(0.0, metrics.baseline as f64),
+            Anchor::Exit => Pt(100.0, metrics.baseline as f64),
+        };
+    };
+    let center = (b.min_x + b.max_x) / 2.0;
+    match anchor {
+        Anchor::Top => Pt(center, b.max_y),
+        Anchor::Bottom => Pt(center, b.min_y),
+        Anchor::AboveRight => Pt(b.max_x, b.max_y),
+        Anchor::Entry => Pt(b.min_x, metrics.baseline as f64),
+        Anchor::Exit => Pt(b.max_x, metrics.baseline as f64),
+    }
+}
+
+// The point of a mark that lands on the base anchor
+fn attach_point(mark: &Glyph, anchor: Anchor) -> Pt<f64> {
+    if let Some(p) = mark.anchors.get(&anchor) {
+        return Pt(p.0 as f64, p.1 as f64);
+    }
+    let Some(b) = ink_bounds(mark) else {
+        return Pt(50.0, 0.0);
+    };
+    let center = (b.min_x + b.max_x) / 2.0;
+    match anchor {
+        Anchor::Top => Pt(center, b.min_y - MARK_GAP),
+        Anchor::Bottom => Pt(center, b.max_y + MARK_GAP),
+        Anchor::AboveRight | Anchor::Exit => Pt(b.min_x, b.min_y),
+        Anchor::Entry => Pt(b.max_x, b.min_y),
+    }
+}
+
+// The far side of a mark, seen from the base
+fn edge_y(bounds: &Bounds, anchor: Anchor) -> f64 {
+    match anchor {
+        Anchor::Bottom => bounds.min_y,
+        Anchor::Top | Anchor::AboveRight | Anchor::Entry | Anchor::Exit => bounds.max_y,
+    }
+}
+
+// Bounds of the strokes in glyph units
+fn ink_bounds(glyph: &Glyph) -> Option<Bounds> {
+    place_strokes(&glyph.strokes, 0.0, 0.0, 100.0, 100.0)
+        .iter()
+        .map(|stroke| stroke.bounds())
+        .reduce(Bounds::union)
+}
@@ -0,0 +1,224 @@
+// Pen travel optimizer for plotter output.
+//
+// Strokes that meet end to end are merged into continuous paths, then the
+// paths are ordered (and flipped where that helps) with a nearest neighbour
+// pass followed by 2-opt, to cut down on pen-up travel.
+use crate::geometry::distance;
+use crate::types::{Pt, Stroke};
+
+#[derive(Debug, Clone)]
+pub struct OptimizeOptions {
+    pub join_tolerance: f64, // Endpoints closer than this are treated as the same point
+    pub start: Pt<f64>,      // Where the pen is before the first path
+    pub max_passes: usize,   // Upper bound on 2-opt improvement passes
+}
+
+impl Default for OptimizeOptions {
+    fn default() -> Self {
+        Self {
+            join_tolerance: 0.01,
+            start: Pt(0.0, 0.0),
+            max_passes: 50,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OptimizedPaths {
+    pub paths: Vec<Vec<Stroke<f64>>>,
+    pub travel_before: f64, // Pen-up distance drawing the strokes in their original order
+    pub travel_after: f64,  // Pen-up distance drawing the optimized paths
+}
+
+impl OptimizedPaths {
+    // All strokes in drawing order
+    pub fn strokes(&self) -> impl Iterator<Item = &Stroke<f64>> {
+        self.paths.iter().flatten()
+    }
+}
+
+// Pen-up distance when drawing strokes in order, starting at start.
+// Gaps shorter than join_tolerance are drawn with the pen down.
+pub fn travel_distance<'a>(
+    strokes: impl IntoIterator<Item = &'a Stroke<f64>>,
+    start: Pt<f64>,
+    join_tolerance: f64,
+) -> f64 {
+    let mut position = start;
+    let mut travel = 0.0;
+    for stroke in strokes {
+        let gap = distance(position, stroke.start_point());
+        if gap >= join_tolerance {
+            travel += gap;
+        }
+        position = stroke.end_point();
+    }
+    travel
+}
+
+pub fn optimize(strokes: &[Stroke<f64>], options: &OptimizeOptions) -> OptimizedPaths {
+    let travel_before = travel_distance(strokes, options.start, options.join_tolerance);
+
+    let paths = merge_paths(strokes, options.join_tolerance);
+    let order = order_paths(&paths, options);
+    let paths: Vec<Vec<Stroke<f64>>> = order
+        .into_iter()
+        .map(|(i, reversed)| {
+            if reversed {
+                reverse_path(&paths[i])
+            } else {
+                paths[i].clone()
+            }
+        })
+        .collect();
+
+    let travel_after = travel_distance(
+        paths.iter().flatten(),
+        options.start,
+        options.join_tolerance,
+    );
+
+    OptimizedPaths {
+        paths,
+        travel_before,
+        travel_after,
+    }
+}
+
+fn reverse_path(path: &[Stroke<f64>]) -> Vec<Stroke<f64>> {
+    path.iter().rev().map(Stroke::reversed).collect()
+}
+
+fn path_start(path: &[Stroke<f64>]) -> Pt<f64> {
+    path[0].start_point()
+}
+
+fn path_end(path: &[Stroke<f64>]) -> Pt<f64> {
+    path[path.len() - 1].end_point()
+}
+
+// Chain strokes whose endpoints coincide, flipping strokes so each path is continuous
+fn merge_paths(strokes: &[Stroke<f64>], tolerance: f64) -> Vec<Vec<Stroke<f64>>> {
+    let mut used = vec![false; strokes.len()];
+    let mut paths = Vec::new();
+
+    for first in 0..strokes.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+        let mut path = vec![strokes[first].clone()];
+
+        // Grow the end of the path, then the front
+        for at_end in [true, false] {
+            loop {
+                let anchor = if at_end {
+                    path_end(&path)
+                } else {
+                    path_start(&path)
+                };
+                let next = (0..strokes.len()).filter(|&i| !used[i]).find_map(|i| {
+                    let (start, end) = (strokes[i].start_point(), strokes[i].end_point());
+                    if distance(anchor, start) < tolerance {
+                        Some((i, !at_end))
+                    } else if distance(anchor, end) < tolerance {
+                        Some((i, at_end))
+                    } else {
+                        None
+                    }
+                });
+
+                let Some((i, reverse)) = next else {
+                    break;
+                };
+                used[i] = true;
+                let stroke = if reverse {
+                    strokes[i].reversed()
+                } else {
+                    strokes[i].clone()
+                };
+                if at_end {
+                    path.push(stroke);
+                } else {
+                    path.insert(0, stroke);
+                }
+            }
+        }
+
+        paths.push(path);
+    }
+
+    paths
+}
+
+// Visiting order as (path index, reversed)
+fn order_paths(paths: &[Vec<Stroke<f64>>], options: &OptimizeOptions) -> Vec<(usize, bool)> {
+    let entry = |(i, reversed): (usize, bool)| {
+        if reversed {
+            path_end(&paths[i])
+        } else {
+            path_start(&paths[i])
+        }
+    };
+    let exit = |(i, reversed): (usize, bool)| {
+        if reversed {
+            path_start(&paths[i])
+        } else {
+            path_end(&paths[i])
+        }
+    };
+
+    // Nearest neighbour, trying both directions of every path
+    let mut order = Vec::with_capacity(paths.len());
+    let mut visited = vec![false; paths.len()];
+    let mut position = options.start;
+    for _ in 0..paths.len() {
+        let mut best: Option<((usize, bool), f64)> = None;
+        for i in (0..paths.len()).filter(|&i| !visited[i]) {
+            for reversed in [false, true] {
+                let d = distance(position, entry((i, reversed)));
+                if best.is_none_or(|(_, best_d)| d < best_d) {
+                    best = Some(((i, reversed), d));
+                }
+            }
+        }
+        let (next, _) = best.expect("unvisited path");
+        visited[next.0] = true;
+        position = exit(next);
+        order.push(next);
+    }
+
+    // 2-opt: reversing the run order[i..=j] flips every path in it and
+    // only changes the two travel moves at its ends
+    for _ in 0..options.max_passes {
+        let mut improved = false;
+        for i in 0..order.len() {
+            for j in i..order.len() {
+                let before = if i == 0 {
+                    options.start
+                } else {
+                    exit(order[i - 1])
+                };
+                let after = order.get(j + 1).map(|next| entry(*next));
+
+                let old_cost = distance(before, entry(order[i]))
+                    + after.map_or(0.0, |a| distance(exit(order[j]), a));
+                let new_cost = distance(before, exit(order[j]))
+                    + after.map_or(0.0, |a| distance(entry(order[i]), a));
+
+                if new_cost + 1e-9 < old_cost {
+                    order[i..=j].reverse();
+                    for step in &mut order[i..=j] {
+                        step.1 = !step.1;
+                    }
+                    improved = true;
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+
+    order
+}
@@ -0,0 +1,311 @@
+// Text on a path.
+//
+// The text is laid out on a straight baseline with TextLayout first, then
+// every glyph is moved onto the path: layout x becomes the arc-length offset
+// along the path and layout y the distance to the left of it. Rigid glyphs are
+// rotated to the path direction as a whole, bent glyphs are flattened and have
+// every point mapped so their strokes follow the curve.
+use crate::geometry::{distance, lerp, normalize};
+use crate::layout::{MissingGlyph, PlacedGlyph, TextLayout};
+use crate::transform::Transform;
+use crate::types::{Font, Pt, Stroke};
+
+// Points closer than this are merged when building a path
+const MIN_SEGMENT: f64 = 1e-9;
+
+// A polyline measured by arc length
+#[derive(Debug, Clone)]
+pub struct TextPath {
+    points: Vec<Pt<f64>>,
+    distances: Vec<f64>, // arc length at each point
+    closed: bool,
+}
+
+impl TextPath {
+    // Any outline, e.g. day-1's Star::points() mapped to Pt<f64>. A closed path
+    // goes back to the first point and offsets wrap around it.
+    pub fn from_points(points: &[Pt<f64>], closed: bool) -> Self {
+        let mut deduped: Vec<Pt<f64>> = Vec::with_capacity(points.len() + 1);
+        for &p in points {
+            if deduped
+                .last()
+                .is_none_or(|&last| distance(last, p) > MIN_SEGMENT)
+            {
+                deduped.push(p);
+            }
+        }
+        if closed && deduped.len() > 1 {
+            let first = deduped[0];
+            if distance(first, *deduped.last().unwrap()) > MIN_SEGMENT {
+                deduped.push(first);
+            } else {
+                *deduped.last_mut().unwrap() = first;
+            }
+        }
+
+        let mut distances = Vec::with_capacity(deduped.len());
+        let mut total = 0.0;
+        for (i, &p) in deduped.iter().enumerate() {
+            if i > 0 {
+                total += distance(deduped[i - 1], p);
+            }
+            distances.push(total);
+        }
+
+        Self {
+            points: deduped,
+            distances,
+            closed,
+        }
+    }
+
+    // Strokes are flattened and joined in order, the path is closed when the
+    // last stroke ends where the first one starts
+    pub fn from_strokes(strokes: &[Stroke<f64>], tolerance: f64) -> Self {
+        let mut points = Vec::new();
+        for stroke in strokes {
+            points.extend(stroke.flatten(tolerance));
+        }
+        let closed = match (strokes.first(), strokes.last()) {
+            (Some(first), Some(last)) => {
+                distance(first.start_point(), last.end_point()) <= tolerance
+            }
+            _ => false,
+        };
+        Self::from_points(&points, closed)
+    }
+
+    // The same path walked the other way, flips which side the text sits on
+    pub fn reversed(&self) -> Self {
+        let points: Vec<_> = self.points.iter().rev().copied().collect();
+        Self::from_points(&points, self.closed)
+    }
+
+    pub fn length(&self) -> f64 {
+        self.distances.last().copied().unwrap_or(0.0)
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    // Segment index and position along it for an arc-length offset. Closed
+    // paths wrap, open ones extend past their ends in a straight line.
+    fn locate(&self, s: f64) -> (usize, f64) {
+        let length = self.length();
+        let s = if self.closed && length > 0.0 {
+            s.rem_euclid(length)
+        } else {
+            s
+        };
+        let last = self.points.len().saturating_sub(2);
+        let segment = self
+            .distances
+            .partition_point(|&d| d <= s)
+            .saturating_sub(1)
+            .min(last);
+        let seg_len = self.distances.get(segment + 1).copied().unwrap_or(0.0)
+            - self.distances.get(segment).copied().unwrap_or(0.0);
+        let t = if seg_len > 0.0 {
+            (s - self.distances[segment]) / seg_len
+        } else {
+            0.0
+        };
+        (segment, t)
+    }
+
+    pub fn point_at(&self, s: f64) -> Pt<f64> {
+        match self.points.len() {
+            0 => Pt(0.0, 0.0),
+            1 => Pt(self.points[0].0 + s, self.points[0].1),
+            _ => {
+                let (segment, t) = self.locate(s);
+                lerp(self.points[segment], self.points[segment + 1], t)
+            }
+        }
+    }
+
+    fn segment_direction(&self, segment: usize) -> Pt<f64> {
+        let (a, b) = (self.points[segment], self.points[segment + 1]);
+        normalize(Pt(b.0 - a.0, b.1 - a.1))
+    }
+
+    // Unit direction of the segment at s
+    pub fn tangent_at(&self, s: f64) -> Pt<f64> {
+        if self.points.len() < 2 {
+            return Pt(1.0, 0.0);
+        }
+        self.segment_direction(self.locate(s).0)
+    }
+
+    // Unit normal to the left of the direction of travel. Blended between the
+    // corners so offset points move smoothly around them.
+    pub fn normal_at(&self, s: f64) -> Pt<f64> {
+        if self.points.len() < 2 {
+            return Pt(0.0, 1.0);
+        }
+        let (segment, t) = self.locate(s);
+        let a = self.corner_direction(segment);
+        let b = self.corner_direction(segment + 1);
+        let d = normalize(lerp(a, b, t));
+        Pt(-d.1, d.0)
+    }
+
+    // Average direction of the two segments meeting at a point
+    fn corner_direction(&self, point: usize) -> Pt<f64> {
+        let segments = self.points.len() - 1;
+        let before = match point {
+            0 if self.closed => Some(segments - 1),
+            0 => None,
+            p => Some(p - 1),
+        };
+        let after = match point {
+            p if p < segments => Some(p),
+            _ if self.closed => Some(0),
+            _ => None,
+        };
+        let dir = match (before, after) {
+            (Some(b), Some(a)) => {
+                let (b, a) = (self.segment_direction(b), self.segment_direction(a));
+                Pt(b.0 + a.0, b.1 + a.1)
+            }
+            (Some(s), None) | (None, Some(s)) => self.segment_direction(s),
+            (None, None) => Pt(1.0, 0.0),
+        };
+        // Segments doubling back on themselves cancel out
+        match normalize(dir) {
+            Pt(x, y) if x == 0.0 && y == 0.0 => self.segment_direction(after.or(before).unwrap()),
+            d => d,
+        }
+    }
+
+    // Layout coordinates to the page, x along the path and y to the left of it
+    pub fn map(&self, p: Pt<f64>) -> Pt<f64> {
+        let on_path = self.point_at(p.0);
+        let normal = self.normal_at(p.0);
+        Pt(on_path.0 + normal.0 * p.1, on_path.1 + normal.1 * p.1)
+    }
+}
+
+// Glyphs placed along a path. There are no lines or text box like in a
+// LayoutResult, the glyphs turn with the path so a straight layout's
+// measurements don't fit them.
+#[derive(Debug, Clone)]
+pub struct PathLayoutResult {
+    pub glyphs: Vec<PlacedGlyph>,
+    pub missing: Vec<MissingGlyph>,
+}
+
+impl PathLayoutResult {
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    // All strokes in drawing order
+    pub fn strokes(&self) -> impl Iterator<Item = &Stroke<f64>> {
+        self.glyphs.iter().flat_map(|g| g.strokes.iter())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PathLayout {
+    // x is the offset along the path where the text starts, y the baseline's
+    // distance to the left of the path
+    pub text: TextLayout,
+    pub bend: bool,
+    pub tolerance: f64, // flattening tolerance for bent glyphs
+    pub bend_step: f64, // longest straight piece of a bent stroke
+}
+
+impl PathLayout {
+    pub fn new(text: TextLayout) -> Self {
+        Self {
+            text,
+            bend: false,
+            tolerance: 0.1,
+            bend_step: 1.0,
+        }
+    }
+
+    pub fn bend(mut self, bend: bool) -> Self {
+        self.bend = bend;
+        self
+    }
+
+    pub fn layout(&self, font: &Font, text: &str, path: &TextPath) -> PathLayoutResult {
+        let straight = self.text.layout(font, text);
+        let mut glyphs = straight.glyphs;
+
+        for glyph in &mut glyphs {
+            if self.bend {
+                self.bend_glyph(glyph, path);
+            } else {
+                place_rigid(glyph, path);
+            }
+        }
+
+        PathLayoutResult {
+            glyphs,
+            missing: straight.missing,
+        }
+    }
+
+    fn bend_glyph(&self, glyph: &mut PlacedGlyph, path: &TextPath) {
+        glyph.strokes = glyph
+            .strokes
+            .iter()
+            .map(|stroke| {
+                let flat = stroke.flatten(self.tolerance);
+                let mut points = Vec::with_capacity(flat.len());
+                for (i, &p) in flat.iter().enumerate() {
+                    if i > 0 {
+                        // Long straight pieces have to be split to follow the curve
+                        let prev = flat[i - 1];
+                        let pieces = (distance(prev, p) / self.bend_step).ceil().max(1.0) as usize;
+                        for k in 1..pieces {
+                            points.push(path.map(lerp(prev, p, k as f64 / pieces as f64)));
+                        }
+                    }
+                    points.push(path.map(p));
+                }
+                Stroke::Polyline { points }
+            })
+            .collect();
+
+        let tangent = path.tangent_at(glyph.x);
+        glyph.angle = tangent.1.atan2(tangent.0).to_degrees();
+        let origin = path.map(Pt(glyph.x, glyph.y));
+        glyph.x = origin.0;
+        glyph.y = origin.1;
+    }
+}
+
+// Moves the glyph without changing its shape. The bottom center of the glyph
+// box goes on the path and the glyph turns to the chord across its width,
+// which keeps it steady on sharp corners.
+fn place_rigid(glyph: &mut PlacedGlyph, path: &TextPath) {
+    let center = glyph.x + glyph.width / 2.0;
+    let anchor = path.map(Pt(center, glyph.y));
+    let from = path.point_at(glyph.x);
+    let to = path.point_at(glyph.x + glyph.width);
+    let chord = normalize(Pt(to.0 - from.0, to.1 - from.1));
+    let (cos, sin) = match chord {
+        Pt(x, y) if x == 0.0 && y == 0.0 => {
+            let t = path.tangent_at(center);
+            (t.0, t.1)
+        }
+        Pt(x, y) => (x, y),
+    };
+
+    let angle = sin.atan2(cos).to_degrees();
+    let transform = Transform::translate(-center, -glyph.y)
+        .then(Transform::rotate(angle))
+        .then(Transform::translate(anchor.0, anchor.1));
+
+    // Rotation keeps arcs as arcs, so the tolerance is never used
+    glyph.strokes = transform.apply_strokes(&glyph.strokes, 0.1);
+    let origin = transform.apply(Pt(glyph.x, glyph.y));
+    glyph.x = origin.0;
+    glyph.y = origin.1;
+    glyph.angle = angle;
+}
@@ -0,0 +1,272 @@
+use crate::clip::{ClipArea, ClipReport};
+use crate::drawing::{Drawing, DrawingOptions};
+use crate::types::{Pt, Stroke};
+
+// Consecutive strokes closer than this (in mm) are drawn without lifting the pen
+const JOIN_TOLERANCE: f64 = 0.01;
+
+// Marlin plotter settings, a servo on P0 moves the pen
+#[derive(Debug, Clone)]
+pub struct GcodeConfig {
+    pub feed_rate: f64,         // Feed rate for movement in mm/min
+    pub pen_up_position: u32,   // Servo angle for pen up
+    pub pen_down_position: u32, // Servo angle for pen down
+    pub pen_delay: u32,         // Delay after pen movement in ms
+    pub bed_width: f64,         // Bed width in mm
+    pub bed_height: f64,        // Bed height in mm
+    pub origin_x: f64,          // X offset from origin in mm
+    pub origin_y: f64,          // Y offset from origin in mm
+    // Where the pen may go in machine coordinates, the bed from the origin
+    // to bed_width x bed_height when None. Strokes are clipped to it.
+    pub work_area: Option<ClipArea>,
+}
+
+impl Default for GcodeConfig {
+    fn default() -> Self {
+        Self {
+            feed_rate: 1000.0,
+            pen_up_position: 90,
+            pen_down_position: 30,
+            pen_delay: 300,
+            bed_width: 300.0,
+            bed_height: 200.0,
+            origin_x: 10.0,
+            origin_y: 10.0,
+            work_area: None,
+        }
+    }
+}
+
+// Marlin G-code implementation of the drawing interface
+pub struct GcodeDrawing {
+    options: DrawingOptions,
+    config: GcodeConfig,
+    commands: Vec<String>,
+    // Current position in machine coordinates
+    current_x: f64,
+    current_y: f64,
+    pen_down: bool,
+    // Work area in drawing coordinates
+    clip_area: ClipArea,
+    clip_report: ClipReport,
+}
+
+// G-code coordinates are rounded to micrometers
+fn fmt(v: f64) -> f64 {
+    (v * 1000.0).round() / 1000.0
+}
+
+impl GcodeDrawing {
+    pub fn new(options: DrawingOptions, config: GcodeConfig) -> Self {
+        let commands = vec![
+            "; Marlin G-code generated by day-5 glyphs".to_string(),
+            "G21 ; Set units to millimeters".to_string(),
+            "G90 ; Set to absolute positioning".to_string(),
+            format!("M280 P0 S{} ; Pen up", config.pen_up_position),
+            format!(
+                "G0 X{} Y{} ; Move to origin",
+                config.origin_x, config.origin_y
+            ),
+            format!("G4 P{} ; Wait for pen to move", config.pen_delay),
+        ];
+
+        let clip_area = match &config.work_area {
+            Some(area) => area.translated(-config.origin_x, -config.origin_y),
+            None => ClipArea::rect(
+                0.0,
+                0.0,
+                config.bed_width - config.origin_x,
+                config.bed_height - config.origin_y,
+            ),
+        };
+
+        Self {
+            options,
+            current_x: config.origin_x,
+            current_y: config.origin_y,
+            config,
+            commands,
+            pen_down: false,
+            clip_area,
+            clip_report: ClipReport::default(),
+        }
+    }
+
+    pub fn config(&self) -> &GcodeConfig {
+        &self.config
+    }
+
+    // What was cut off at the edge of the work area so far
+    pub fn clip_report(&self) -> &ClipReport {
+        &self.clip_report
+    }
+
+    // Offset drawing coordinates by the origin, strokes are already clipped
+    // to the work area so they stay on the bed
+    fn to_machine(&self, x: f64, y: f64) -> (f64, f64) {
+        (self.config.origin_x + x, self.config.origin_y + y)
+    }
+
+    // The parts of a stroke inside the work area
+    fn clip(&mut self, stroke: Stroke<f64>) -> Vec<Stroke<f64>> {
+        let pieces = self.clip_area.clip_stroke(&stroke, self.options.tolerance);
+        self.clip_report.add(&stroke, &pieces);
+        pieces
+    }
+
+    fn pen_up(&mut self) {
+        if self.pen_down {
+            self.commands
+                .push(format!("M280 P0 S{} ; Pen up", self.config.pen_up_position));
+            self.commands.push(format!(
+                "G4 P{} ; Wait for pen to move",
+                self.config.pen_delay
+            ));
+            self.pen_down = false;
+        }
+    }
+
+    fn pen_down(&mut self) {
+        if !self.pen_down {
+            self.commands.push(format!(
+                "M280 P0 S{} ; Pen down",
+                self.config.pen_down_position
+            ));
+            self.commands.push(format!(
+                "G4 P{} ; Wait for pen to move",
+                self.config.pen_delay
+            ));
+            self.pen_down = true;
+        }
+    }
+
+    fn is_current(&self, x: f64, y: f64) -> bool {
+        (self.current_x - x).hypot(self.current_y - y) < JOIN_TOLERANCE
+    }
+
+    // Put the pen down at the start of a stroke, only lifting it when the
+    // stroke doesn't continue from where the last one ended
+    fn start_stroke(&mut self, x: f64, y: f64) {
+        let (x, y) = self.to_machine(x, y);
+        if self.pen_down && self.is_current(x, y) {
+            return;
+        }
+
+        self.pen_up();
+        self.commands.push(format!("G0 X{} Y{}", fmt(x), fmt(y)));
+        self.current_x = x;
+        self.current_y = y;
+        self.pen_down();
+    }
+
+    fn draw_to(&mut self, x: f64, y: f64) {
+        let (x, y) = self.to_machine(x, y);
+        self.commands.push(format!(
+            "G1 X{} Y{} F{}",
+            fmt(x),
+            fmt(y),
+            self.config.feed_rate
+        ));
+        self.current_x = x;
+        self.current_y = y;
+    }
+
+    // Single G2/G3 move, i and j are the center offset from the current position
+    fn arc_to(&mut self, x: f64, y: f64, center_x: f64, center_y: f64, ccw: bool) {
+        let (x, y) = self.to_machine(x, y);
+        let (center_x, center_y) = self.to_machine(center_x, center_y);
+        let command = if ccw { "G3" } else { "G2" };
+        self.commands.push(format!(
+            "{command} X{} Y{} I{} J{} F{}",
+            fmt(x),
+            fmt(y),
+            fmt(center_x - self.current_x),
+            fmt(center_y - self.current_y),
+            self.config.feed_rate
+        ));
+        self.current_x = x;
+        self.current_y = y;
+    }
+
+    fn draw_arc(&mut self, x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64) {
+        let point = |angle: f64| {
+            let rad = angle.to_radians();
+            (x + radius * rad.cos(), y + radius * rad.sin())
+        };
+
+        // An arc that ends where it starts is a full circle to Marlin, so one
+        // too short to see is left out rather than drawn all the way round
+        let sweep = end_angle - start_angle;
+        if radius * sweep.abs().to_radians() < JOIN_TOLERANCE {
+            return;
+        }
+
+        let (start_x, start_y) = point(start_angle);
+        self.start_stroke(start_x, start_y);
+
+        // Split into segments of at most 180 degrees, anything larger
+        // (and full circles in particular) is ambiguous as a single G2/G3
+        let segments = (sweep.abs() / 180.0).ceil().max(1.0) as usize;
+        for i in 1..=segments {
+            let angle = start_angle + sweep * i as f64 / segments as f64;
+            let (end_x, end_y) = point(angle);
+            self.arc_to(end_x, end_y, x, y, sweep > 0.0);
+        }
+    }
+}
+
+impl Drawing for GcodeDrawing {
+    fn options(&self) -> &DrawingOptions {
+        &self.options
+    }
+
+    fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        let line = Stroke::Line {
+            from: Pt(x1, y1),
+            to: Pt(x2, y2),
+        };
+        for piece in self.clip(line) {
+            if let Stroke::Line { from, to } = piece {
+                self.start_stroke(from.0, from.1);
+                self.draw_to(to.0, to.1);
+            }
+        }
+    }
+
+    fn arc(&mut self, x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64) {
+        let arc = Stroke::Arc {
+            center: Pt(x, y),
+            radius,
+            start: start_angle,
+            end: end_angle,
+        };
+        for piece in self.clip(arc) {
+            if let Stroke::Arc { start, end, .. } = piece {
+                self.draw_arc(x, y, radius, start, end);
+            }
+        }
+    }
+
+    fn begin_glyph(&mut self, letter: char) {
+        self.commands.push(format!("\n; Letter: {letter}"));
+    }
+
+    fn render(&mut self) -> String {
+        self.pen_up();
+
+        let mut out = self.commands.clone();
+        if self.clip_report.is_clipped() {
+            out.push(format!(
+                "\n; Clipped {} strokes to the work area, {:.1}mm not drawn",
+                self.clip_report.split + self.clip_report.removed,
+                self.clip_report.clipped_length()
+            ));
+        }
+        out.push(format!(
+            "G0 X{} Y{} ; Return to origin",
+            self.config.origin_x, self.config.origin_y
+        ));
+        out.push("M84 ; Disable motors".to_string());
+        out.join("\n")
+    }
+}
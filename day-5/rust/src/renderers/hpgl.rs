@@ -0,0 +1,206 @@
+use crate::clip::{ClipArea, ClipReport};
+use crate::drawing::{Drawing, DrawingOptions};
+use crate::geometry::arc_sweep;
+use crate::types::{Pt, Stroke};
+
+// Consecutive strokes closer than this (in plotter units) are drawn without lifting the pen
+const JOIN_TOLERANCE: i64 = 1;
+
+// How arcs are sent to the plotter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArcMode {
+    // AA (arc absolute) commands, the plotter interpolates
+    #[default]
+    Native,
+    // PD segments, for plotters without AA support
+    Flatten,
+}
+
+// HP-GL plotter settings
+#[derive(Debug, Clone)]
+pub struct HpglConfig {
+    pub units_per_mm: f64,     // Plotter units per mm, 40 on most HP plotters
+    pub pen: u8,               // Pen number for SP
+    pub velocity: Option<f64>, // Pen speed for VS in cm/s, plotter default if None
+    pub page_width: f64,       // Paper width in mm
+    pub page_height: f64,      // Paper height in mm
+    pub origin_x: f64,         // X offset from origin in mm
+    pub origin_y: f64,         // Y offset from origin in mm
+    pub arc_mode: ArcMode,
+    pub chord_angle: f64, // Chord tolerance in degrees for AA
+}
+
+impl Default for HpglConfig {
+    fn default() -> Self {
+        Self {
+            units_per_mm: 40.0,
+            pen: 1,
+            velocity: None,
+            page_width: 297.0,
+            page_height: 210.0,
+            origin_x: 10.0,
+            origin_y: 10.0,
+            arc_mode: ArcMode::default(),
+            chord_angle: 5.0,
+        }
+    }
+}
+
+// HP-GL implementation of the drawing interface
+pub struct HpglDrawing {
+    options: DrawingOptions,
+    config: HpglConfig,
+    commands: Vec<String>,
+    // Current position in plotter units
+    current: (i64, i64),
+    pen_down: bool,
+    // The paper in drawing coordinates
+    clip_area: ClipArea,
+    clip_report: ClipReport,
+}
+
+impl HpglDrawing {
+    pub fn new(options: DrawingOptions, config: HpglConfig) -> Self {
+        let mut commands = vec!["IN;".to_string(), format!("SP{};", config.pen)];
+        if let Some(velocity) = config.velocity {
+            commands.push(format!("VS{velocity};"));
+        }
+
+        let clip_area = ClipArea::rect(
+            0.0,
+            0.0,
+            config.page_width - config.origin_x,
+            config.page_height - config.origin_y,
+        );
+
+        Self {
+            options,
+            config,
+            commands,
+            current: (0, 0),
+            pen_down: false,
+            clip_area,
+            clip_report: ClipReport::default(),
+        }
+    }
+
+    pub fn config(&self) -> &HpglConfig {
+        &self.config
+    }
+
+    // What was cut off at the edge of the paper so far
+    pub fn clip_report(&self) -> &ClipReport {
+        &self.clip_report
+    }
+
+    // The parts of a stroke on the paper
+    fn clip(&mut self, stroke: Stroke<f64>) -> Vec<Stroke<f64>> {
+        let pieces = self.clip_area.clip_stroke(&stroke, self.options.tolerance);
+        self.clip_report.add(&stroke, &pieces);
+        pieces
+    }
+
+    // Drawing coordinates (mm) to plotter units
+    fn to_plotter(&self, x: f64, y: f64) -> (i64, i64) {
+        (
+            ((self.config.origin_x + x) * self.config.units_per_mm).round() as i64,
+            ((self.config.origin_y + y) * self.config.units_per_mm).round() as i64,
+        )
+    }
+
+    // Put the pen down at the start of a stroke, only lifting it when the
+    // stroke doesn't continue from where the last one ended
+    fn start_stroke(&mut self, x: f64, y: f64) {
+        let (px, py) = self.to_plotter(x, y);
+        let (cx, cy) = self.current;
+        if self.pen_down && (px - cx).abs() <= JOIN_TOLERANCE && (py - cy).abs() <= JOIN_TOLERANCE {
+            return;
+        }
+
+        self.commands.push(format!("PU{px},{py};"));
+        self.current = (px, py);
+        self.pen_down = false;
+    }
+
+    fn draw_to(&mut self, x: f64, y: f64) {
+        let (px, py) = self.to_plotter(x, y);
+        self.commands.push(format!("PD{px},{py};"));
+        self.current = (px, py);
+        self.pen_down = true;
+    }
+
+    fn draw_arc(&mut self, x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64) {
+        let arc = Stroke::Arc {
+            center: Pt(x, y),
+            radius,
+            start: start_angle,
+            end: end_angle,
+        };
+        let start = arc.start_point();
+        self.start_stroke(start.0, start.1);
+
+        match self.config.arc_mode {
+            ArcMode::Native => {
+                // AA draws from the pen position, positive sweeps are counter-clockwise
+                if !self.pen_down {
+                    self.commands.push("PD;".to_string());
+                    self.pen_down = true;
+                }
+                let (cx, cy) = self.to_plotter(x, y);
+                self.commands.push(format!(
+                    "AA{cx},{cy},{},{};",
+                    arc_sweep(start_angle, end_angle),
+                    self.config.chord_angle
+                ));
+                let end = arc.end_point();
+                self.current = self.to_plotter(end.0, end.1);
+            }
+            ArcMode::Flatten => {
+                let tolerance = self.options.tolerance;
+                for p in arc.flatten(tolerance).into_iter().skip(1) {
+                    self.draw_to(p.0, p.1);
+                }
+            }
+        }
+    }
+}
+
+impl Drawing for HpglDrawing {
+    fn options(&self) -> &DrawingOptions {
+        &self.options
+    }
+
+    fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        let line = Stroke::Line {
+            from: Pt(x1, y1),
+            to: Pt(x2, y2),
+        };
+        for piece in self.clip(line) {
+            if let Stroke::Line { from, to } = piece {
+                self.start_stroke(from.0, from.1);
+                self.draw_to(to.0, to.1);
+            }
+        }
+    }
+
+    fn arc(&mut self, x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64) {
+        let arc = Stroke::Arc {
+            center: Pt(x, y),
+            radius,
+            start: start_angle,
+            end: end_angle,
+        };
+        for piece in self.clip(arc) {
+            if let Stroke::Arc { start, end, .. } = piece {
+                self.draw_arc(x, y, radius, start, end);
+            }
+        }
+    }
+
+    fn render(&mut self) -> String {
+        let mut out = self.commands.clone();
+        out.push("PU;".to_string());
+        out.push("SP0;".to_string());
+        out.join("\n")
+    }
+}
@@ -0,0 +1,11 @@
+pub mod gcode;
+pub mod hpgl;
+pub mod png;
+pub mod postscript;
+pub mod svg;
+
+pub use gcode::{GcodeConfig, GcodeDrawing};
+pub use hpgl::{ArcMode, HpglConfig, HpglDrawing};
+pub use png::{LineCap, LineJoin, PngConfig, PngDrawing, PngError};
+pub use postscript::{ColorSpace, PostScriptDrawing};
+pub use svg::SvgDrawing;
@@ -0,0 +1,280 @@
+use std::fmt;
+use std::path::Path;
+
+use tiny_skia as skia;
+
+use crate::drawing::{Drawing, DrawingOptions};
+use crate::geometry::arc_sweep;
+use crate::types::Pt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineCap {
+    Butt,
+    #[default]
+    Round,
+    Square,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineJoin {
+    Miter,
+    #[default]
+    Round,
+    Bevel,
+}
+
+// Raster settings, the page is measured in points like the SVG and PostScript output
+#[derive(Debug, Clone)]
+pub struct PngConfig {
+    pub width: f64,
+    pub height: f64,
+    pub dpi: f64,                     // 72 gives one pixel per point
+    pub background: Option<[f64; 3]>, // rgb, 0-1 range, transparent if None
+    pub line_cap: LineCap,
+    pub line_join: LineJoin,
+    pub anti_alias: bool,
+}
+
+impl Default for PngConfig {
+    fn default() -> Self {
+        Self {
+            width: 612.0,
+            height: 792.0,
+            dpi: 72.0,
+            background: Some([1.0, 1.0, 1.0]),
+            line_cap: LineCap::default(),
+            line_join: LineJoin::default(),
+            anti_alias: true,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum PngError {
+    // Zero sized or more pixels than tiny-skia can allocate
+    InvalidSize(u32, u32),
+    Encode(png::EncodingError),
+}
+
+impl fmt::Display for PngError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PngError::InvalidSize(width, height) => {
+                write!(f, "cannot rasterize a {width}x{height} pixel image")
+            }
+            PngError::Encode(err) => write!(f, "failed to encode PNG: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for PngError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PngError::InvalidSize(..) => None,
+            PngError::Encode(err) => Some(err),
+        }
+    }
+}
+
+impl From<png::EncodingError> for PngError {
+    fn from(err: png::EncodingError) -> Self {
+        PngError::Encode(err)
+    }
+}
+
+// Rasterizes strokes on the CPU as they are drawn
+pub struct PngDrawing {
+    options: DrawingOptions,
+    config: PngConfig,
+    pixmap: skia::Pixmap,
+    paint: skia::Paint<'static>,
+    stroke: skia::Stroke,
+    // Page points to pixels, with y flipped
+    transform: skia::Transform,
+}
+
+impl PngDrawing {
+    pub fn new(options: DrawingOptions, config: PngConfig) -> Result<Self, PngError> {
+        let scale = config.dpi / 72.0;
+        let width = (config.width * scale).round() as u32;
+        let height = (config.height * scale).round() as u32;
+        let mut pixmap =
+            skia::Pixmap::new(width, height).ok_or(PngError::InvalidSize(width, height))?;
+        if let Some(background) = config.background {
+            pixmap.fill(color(background));
+        }
+
+        let mut paint = skia::Paint::default();
+        paint.set_color(color(options.color));
+        paint.anti_alias = config.anti_alias;
+
+        let stroke = skia::Stroke {
+            width: options.line_width as f32,
+            line_cap: match config.line_cap {
+                LineCap::Butt => skia::LineCap::Butt,
+                LineCap::Round => skia::LineCap::Round,
+                LineCap::Square => skia::LineCap::Square,
+            },
+            line_join: match config.line_join {
+                LineJoin::Miter => skia::LineJoin::Miter,
+                LineJoin::Round => skia::LineJoin::Round,
+                LineJoin::Bevel => skia::LineJoin::Bevel,
+            },
+            ..Default::default()
+        };
+
+        let scale = scale as f32;
+        let transform = skia::Transform::from_row(scale, 0.0, 0.0, -scale, 0.0, height as f32);
+
+        Ok(Self {
+            options,
+            config,
+            pixmap,
+            paint,
+            stroke,
+            transform,
+        })
+    }
+
+    pub fn config(&self) -> &PngConfig {
+        &self.config
+    }
+
+    pub fn pixmap(&self) -> &skia::Pixmap {
+        &self.pixmap
+    }
+
+    pub fn encode_png(&self) -> Result<Vec<u8>, PngError> {
+        Ok(self.pixmap.encode_png()?)
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<(), PngError> {
+        Ok(self.pixmap.save_png(path)?)
+    }
+
+    fn stroke_path(&mut self, build: impl FnOnce(&mut skia::PathBuilder)) {
+        let mut builder = skia::PathBuilder::new();
+        build(&mut builder);
+        // Zero length paths come back as None and draw nothing
+        if let Some(path) = builder.finish() {
+            self.pixmap
+                .stroke_path(&path, &self.paint, &self.stroke, self.transform, None);
+        }
+    }
+}
+
+fn color([r, g, b]: [f64; 3]) -> skia::Color {
+    skia::Color::from_rgba(
+        r.clamp(0.0, 1.0) as f32,
+        g.clamp(0.0, 1.0) as f32,
+        b.clamp(0.0, 1.0) as f32,
+        1.0,
+    )
+    .unwrap()
+}
+
+impl Drawing for PngDrawing {
+    fn options(&self) -> &DrawingOptions {
+        &self.options
+    }
+
+    fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        self.stroke_path(|path| {
+            path.move_to(x1 as f32, y1 as f32);
+            path.line_to(x2 as f32, y2 as f32);
+        });
+    }
+
+    // tiny-skia has no arcs, they are drawn as cubics of at most 90 degrees
+    fn arc(&mut self, x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64) {
+        let sweep = arc_sweep(start_angle, end_angle).clamp(-360.0, 360.0);
+        let pieces = (sweep.abs() / 90.0).ceil().max(1.0) as usize;
+        let step = (sweep / pieces as f64).to_radians();
+        // Control point distance along the tangent for a cubic arc
+        let k = 4.0 / 3.0 * (step / 4.0).tan() * radius;
+        let at = |angle: f64| (x + radius * angle.cos(), y + radius * angle.sin());
+
+        self.stroke_path(|path| {
+            let start = start_angle.to_radians();
+            let (sx, sy) = at(start);
+            path.move_to(sx as f32, sy as f32);
+            for i in 0..pieces {
+                let a0 = start + step * i as f64;
+                let a1 = a0 + step;
+                let (x0, y0) = at(a0);
+                let (x1, y1) = at(a1);
+                path.cubic_to(
+                    (x0 - k * a0.sin()) as f32,
+                    (y0 + k * a0.cos()) as f32,
+                    (x1 + k * a1.sin()) as f32,
+                    (y1 - k * a1.cos()) as f32,
+                    x1 as f32,
+                    y1 as f32,
+                );
+            }
+        });
+    }
+
+    fn quadratic(&mut self, from: Pt<f64>, ctrl: Pt<f64>, to: Pt<f64>) {
+        self.stroke_path(|path| {
+            path.move_to(from.0 as f32, from.1 as f32);
+            path.quad_to(ctrl.0 as f32, ctrl.1 as f32, to.0 as f32, to.1 as f32);
+        });
+    }
+
+    fn cubic(&mut self, from: Pt<f64>, ctrl1: Pt<f64>, ctrl2: Pt<f64>, to: Pt<f64>) {
+        self.stroke_path(|path| {
+            path.move_to(from.0 as f32, from.1 as f32);
+            path.cubic_to(
+                ctrl1.0 as f32,
+                ctrl1.1 as f32,
+                ctrl2.0 as f32,
+                ctrl2.1 as f32,
+                to.0 as f32,
+                to.1 as f32,
+            );
+        });
+    }
+
+    // One path so the joins between segments are drawn
+    fn polyline(&mut self, points: &[Pt<f64>]) {
+        let Some((first, rest)) = points.split_first() else {
+            return;
+        };
+        self.stroke_path(|path| {
+            path.move_to(first.0 as f32, first.1 as f32);
+            for p in rest {
+                path.line_to(p.0 as f32, p.1 as f32);
+            }
+        });
+    }
+
+    // The image as a data URI, ready for HTML or markdown. Use encode_png or
+    // save_png for the raw file.
+    fn render(&mut self) -> String {
+        // Encoding into memory only fails on invalid image sizes, which new rejects
+        let png = self.encode_png().expect("PNG encoding failed");
+        format!("data:image/png;base64,{}", base64(&png))
+    }
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
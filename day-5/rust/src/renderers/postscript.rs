@@ -0,0 +1,132 @@
+use crate::drawing::{Drawing, DrawingOptions};
+use crate::geometry::quadratic_to_cubic;
+use crate::types::Pt;
+
+// Which color operator to emit for the stroke color
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorSpace {
+    Gray,
+    #[default]
+    Rgb,
+}
+
+// PostScript implementation of the drawing interface
+pub struct PostScriptDrawing {
+    options: DrawingOptions,
+    width: f64,
+    height: f64,
+    color_space: ColorSpace,
+    commands: Vec<String>,
+}
+
+impl PostScriptDrawing {
+    pub fn new(options: DrawingOptions) -> Self {
+        Self::with_page(options, 612.0, 792.0)
+    }
+
+    pub fn with_page(options: DrawingOptions, width: f64, height: f64) -> Self {
+        Self {
+            options,
+            width,
+            height,
+            color_space: ColorSpace::default(),
+            commands: Vec::new(),
+        }
+    }
+
+    pub fn color_space(mut self, color_space: ColorSpace) -> Self {
+        self.color_space = color_space;
+        self
+    }
+
+    fn header(&self) -> Vec<String> {
+        let [r, g, b] = self.options.color;
+        let color = match self.color_space {
+            // Rec. 601 luma, the same weights printers use for gray conversion
+            ColorSpace::Gray => format!("{} setgray", 0.299 * r + 0.587 * g + 0.114 * b),
+            ColorSpace::Rgb => format!("{r} {g} {b} setrgbcolor"),
+        };
+
+        vec![
+            "%!PS-Adobe-3.0".to_string(),
+            "%%Creator: day-5 glyphs".to_string(),
+            format!(
+                "%%BoundingBox: 0 0 {} {}",
+                self.width.ceil(),
+                self.height.ceil()
+            ),
+            "%%Pages: 1".to_string(),
+            "%%EndComments".to_string(),
+            "%%Page: 1 1".to_string(),
+            format!("{} setlinewidth", self.options.line_width),
+            color,
+        ]
+    }
+}
+
+impl Drawing for PostScriptDrawing {
+    fn options(&self) -> &DrawingOptions {
+        &self.options
+    }
+
+    fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        self.commands.push("newpath".to_string());
+        self.commands.push(format!("{x1} {y1} moveto"));
+        self.commands.push(format!("{x2} {y2} lineto"));
+        self.commands.push("stroke".to_string());
+    }
+
+    // arc sweeps counter-clockwise, arcn clockwise, so the direction matches the svg renderer
+    fn arc(&mut self, x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64) {
+        let op = if start_angle < end_angle {
+            "arc"
+        } else {
+            "arcn"
+        };
+        self.commands.push("newpath".to_string());
+        self.commands
+            .push(format!("{x} {y} {radius} {start_angle} {end_angle} {op}"));
+        self.commands.push("stroke".to_string());
+    }
+
+    fn quadratic(&mut self, from: Pt<f64>, ctrl: Pt<f64>, to: Pt<f64>) {
+        let (ctrl1, ctrl2) = quadratic_to_cubic(from, ctrl, to);
+        self.cubic(from, ctrl1, ctrl2, to);
+    }
+
+    fn cubic(&mut self, from: Pt<f64>, ctrl1: Pt<f64>, ctrl2: Pt<f64>, to: Pt<f64>) {
+        self.commands.push("newpath".to_string());
+        self.commands.push(format!("{} {} moveto", from.0, from.1));
+        self.commands.push(format!(
+            "{} {} {} {} {} {} curveto",
+            ctrl1.0, ctrl1.1, ctrl2.0, ctrl2.1, to.0, to.1
+        ));
+        self.commands.push("stroke".to_string());
+    }
+
+    fn polyline(&mut self, points: &[Pt<f64>]) {
+        let Some((first, rest)) = points.split_first() else {
+            return;
+        };
+        self.commands.push("newpath".to_string());
+        self.commands
+            .push(format!("{} {} moveto", first.0, first.1));
+        for p in rest {
+            self.commands.push(format!("{} {} lineto", p.0, p.1));
+        }
+        self.commands.push("stroke".to_string());
+    }
+
+    fn begin_glyph(&mut self, letter: char) {
+        self.commands.push(format!("\n% Letter: {letter}"));
+    }
+
+    fn render(&mut self) -> String {
+        let mut out = self.header();
+        out.extend(self.commands.iter().cloned());
+        out.push("showpage".to_string());
+        out.push("%%Trailer".to_string());
+        out.push("%%EOF".to_string());
+        out.join("\n")
+    }
+}
@@ -0,0 +1,169 @@
+use crate::drawing::{Drawing, DrawingOptions};
+use crate::types::Pt;
+
+// SVG implementation of the drawing interface
+pub struct SvgDrawing {
+    options: DrawingOptions,
+    height: f64,
+    stroke_style: String,
+    commands: Vec<String>,
+    glyph_count: usize,
+    group_open: bool,
+}
+
+impl SvgDrawing {
+    pub fn new(options: DrawingOptions) -> Self {
+        Self::with_page(options, 612.0, 792.0, "white")
+    }
+
+    pub fn with_page(
+        options: DrawingOptions,
+        width: f64,
+        height: f64,
+        background_color: &str,
+    ) -> Self {
+        let commands = vec![
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>"#.to_string(),
+            format!(
+                r#"<svg width="{width}" height="{height}" xmlns="http://www.w3.org/2000/svg">"#
+            ),
+            // Background rectangle
+            format!(r#"<rect width="100%" height="100%" fill="{background_color}" />"#),
+        ];
+
+        // Convert color from 0-1 range to 0-255 range for SVG
+        let [r, g, b] = options.color.map(|c| (c * 255.0) as u8);
+        let stroke_style = format!(
+            r#"stroke="rgb({r},{g},{b})" stroke-width="{}" fill="none""#,
+            options.line_width
+        );
+
+        Self {
+            options,
+            height,
+            stroke_style,
+            commands,
+            glyph_count: 0,
+            group_open: false,
+        }
+    }
+
+    fn flip(&self, p: Pt<f64>) -> Pt<f64> {
+        Pt(p.0, self.height - p.1)
+    }
+
+    fn close_group(&mut self) {
+        if self.group_open {
+            self.commands.push("</g>".to_string());
+            self.group_open = false;
+        }
+    }
+}
+
+impl Drawing for SvgDrawing {
+    fn options(&self) -> &DrawingOptions {
+        &self.options
+    }
+
+    fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        // Flip Y-coordinates
+        let y1 = self.height - y1;
+        let y2 = self.height - y2;
+        self.commands.push(format!(
+            r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" {} />"#,
+            self.stroke_style
+        ));
+    }
+
+    fn arc(&mut self, x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64) {
+        // Full circles use the circle element
+        if (end_angle - start_angle).abs() >= 359.9 {
+            self.circle(x, y, radius);
+            return;
+        }
+
+        let start_rad = start_angle.to_radians();
+        let end_rad = end_angle.to_radians();
+
+        // Start and end points, with Y flipped
+        let start_x = x + radius * start_rad.cos();
+        let start_y = self.height - (y + radius * start_rad.sin());
+        let end_x = x + radius * end_rad.cos();
+        let end_y = self.height - (y + radius * end_rad.sin());
+
+        // Large or small arc (> 180 degrees)
+        let large_arc = u8::from((end_angle - start_angle).abs() > 180.0);
+
+        // When flipping Y the sweep flag (direction) is reversed as well
+        let sweep_flag = u8::from(start_angle >= end_angle);
+
+        self.commands.push(format!(
+            r#"<path d="M {start_x},{start_y} A {radius},{radius} 0 {large_arc},{sweep_flag} {end_x},{end_y}" {} />"#,
+            self.stroke_style
+        ));
+    }
+
+    fn circle(&mut self, x: f64, y: f64, radius: f64) {
+        let y = self.height - y;
+        self.commands.push(format!(
+            r#"<circle cx="{x}" cy="{y}" r="{radius}" {} />"#,
+            self.stroke_style
+        ));
+    }
+
+    fn quadratic(&mut self, from: Pt<f64>, ctrl: Pt<f64>, to: Pt<f64>) {
+        let [from, ctrl, to] = [from, ctrl, to].map(|p| self.flip(p));
+        self.commands.push(format!(
+            r#"<path d="M {},{} Q {},{} {},{}" {} />"#,
+            from.0, from.1, ctrl.0, ctrl.1, to.0, to.1, self.stroke_style
+        ));
+    }
+
+    fn cubic(&mut self, from: Pt<f64>, ctrl1: Pt<f64>, ctrl2: Pt<f64>, to: Pt<f64>) {
+        let [from, ctrl1, ctrl2, to] = [from, ctrl1, ctrl2, to].map(|p| self.flip(p));
+        self.commands.push(format!(
+            r#"<path d="M {},{} C {},{} {},{} {},{}" {} />"#,
+            from.0, from.1, ctrl1.0, ctrl1.1, ctrl2.0, ctrl2.1, to.0, to.1, self.stroke_style
+        ));
+    }
+
+    fn polyline(&mut self, points: &[Pt<f64>]) {
+        let points: Vec<String> = points
+            .iter()
+            .map(|p| {
+                let p = self.flip(*p);
+                format!("{},{}", p.0, p.1)
+            })
+            .collect();
+        self.commands.push(format!(
+            r#"<polyline points="{}" {} />"#,
+            points.join(" "),
+            self.stroke_style
+        ));
+    }
+
+    // Every glyph gets its own group, ids are indexed so repeated letters stay unique
+    fn begin_glyph(&mut self, letter: char) {
+        self.close_group();
+
+        let name = if letter.is_ascii_alphanumeric() {
+            letter.to_string()
+        } else {
+            format!("u{:04x}", letter as u32)
+        };
+
+        self.commands.push(format!("\n<!-- Letter: {letter} -->"));
+        self.commands
+            .push(format!(r#"<g id="letter-{}-{name}">"#, self.glyph_count));
+        self.glyph_count += 1;
+        self.group_open = true;
+    }
+
+    fn render(&mut self) -> String {
+        self.close_group();
+
+        let mut out = self.commands.clone();
+        out.push("</svg>".to_string());
+        out.join("\n")
+    }
+}
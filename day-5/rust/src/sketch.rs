@@ -0,0 +1,226 @@
+// Hand drawn look for strokes, something like rough.js.
+//
+// Every stroke is flattened and walked again with the ends moved a bit,
+// running on past them, and a wobble along the way from smoothed noise.
+// Short strokes, like the dots on i and j, get less of it so they keep their
+// shape. With double set every stroke is drawn twice, each time a bit
+// differently. Everything random comes from the seed and the stroke's place
+// in the list, so a drawing looks the same every time and in every backend.
+use crate::geometry::{distance, lerp, normalize};
+use crate::layout::LayoutResult;
+use crate::substitution::random;
+use crate::types::{Pt, Stroke};
+
+#[derive(Debug, Clone)]
+pub struct SketchConfig {
+    pub roughness: f64,  // scales everything below, 0 leaves strokes as they are
+    pub jitter: f64,     // furthest the ends of a stroke move
+    pub overshoot: f64,  // longest the pen runs on past an end
+    pub wobble: f64,     // furthest the path strays sideways
+    pub wavelength: f64, // between wobbles, shorter strokes get less of everything
+    pub double: bool,    // draw every stroke twice
+    pub tolerance: f64,  // for flattening curves
+}
+
+impl Default for SketchConfig {
+    fn default() -> Self {
+        Self {
+            roughness: 1.0,
+            jitter: 1.0,
+            overshoot: 2.0,
+            wobble: 0.8,
+            wavelength: 25.0,
+            double: false,
+            tolerance: 0.1,
+        }
+    }
+}
+
+impl SketchConfig {
+    // The defaults are in points, this puts the distances in other units,
+    // mm for plotters, so a drawing comes out as rough in every backend
+    pub fn scaled(&self, factor: f64) -> Self {
+        Self {
+            jitter: self.jitter * factor,
+            overshoot: self.overshoot * factor,
+            wobble: self.wobble * factor,
+            wavelength: self.wavelength * factor,
+            tolerance: self.tolerance * factor,
+            ..self.clone()
+        }
+    }
+}
+
+// Sketches the strokes of every glyph in place, numbered in drawing order
+pub fn sketch_layout(result: &mut LayoutResult, config: &SketchConfig, seed: u64) {
+    let mut index = 0;
+    for glyph in &mut result.glyphs {
+        let mut strokes = Vec::with_capacity(glyph.strokes.len());
+        for stroke in &glyph.strokes {
+            strokes.extend(sketch_stroke(stroke, config, seed, index));
+            index += 1;
+        }
+        glyph.strokes = strokes;
+    }
+}
+
+pub fn sketch(strokes: &[Stroke<f64>], config: &SketchConfig, seed: u64) -> Vec<Stroke<f64>> {
+    strokes
+        .iter()
+        .enumerate()
+        .flat_map(|(index, stroke)| sketch_stroke(stroke, config, seed, index))
+        .collect()
+}
+
+// One polyline per pass, index is the stroke's place in the drawing
+pub fn sketch_stroke(
+    stroke: &Stroke<f64>,
+    config: &SketchConfig,
+    seed: u64,
+    index: usize,
+) -> Vec<Stroke<f64>> {
+    let points = resample(&stroke.flatten(config.tolerance), config.wavelength / 8.0);
+    let passes = if config.double { 2 } else { 1 };
+    (0..passes)
+        .map(|pass| Stroke::Polyline {
+            points: sketch_points(&points, config, random(seed, index * 2 + pass)),
+        })
+        .collect()
+}
+
+fn sketch_points(points: &[Pt<f64>], config: &SketchConfig, seed: u64) -> Vec<Pt<f64>> {
+    let mut rng = Rng::new(seed);
+    let (Some(&first), Some(&last)) = (points.first(), points.last()) else {
+        return Vec::new();
+    };
+    let lengths = arc_lengths(points);
+    let length = lengths[lengths.len() - 1];
+    let amount = config.roughness * (length / config.wavelength).min(1.0);
+    if amount <= 0.0 {
+        return points.to_vec();
+    }
+
+    let start_jitter = Pt(rng.signed(), rng.signed());
+    let end_jitter = Pt(rng.signed(), rng.signed());
+    let jitter = |t: f64| {
+        let p = lerp(start_jitter, end_jitter, t);
+        Pt(p.0 * config.jitter * amount, p.1 * config.jitter * amount)
+    };
+    let noise = Noise::new(rng.next());
+    let wobble = |s: f64| noise.at(s / config.wavelength) * config.wobble * amount;
+
+    // The pen starts a little before the first point and stops a little after the last
+    let before = rng.unit() * config.overshoot * amount;
+    let after = rng.unit() * config.overshoot * amount;
+    let start_direction = direction(points, 0);
+    let end_direction = direction(points, points.len() - 1);
+
+    let mut sketched = Vec::with_capacity(points.len() + 2);
+    let mut push = |p: Pt<f64>, s: f64, along: Pt<f64>| {
+        let t = if length > 0.0 { s / length } else { 0.0 };
+        let (offset, side) = (jitter(t), wobble(s));
+        sketched.push(Pt(
+            p.0 + offset.0 - along.1 * side,
+            p.1 + offset.1 + along.0 * side,
+        ));
+    };
+    if before > 0.0 {
+        let p = Pt(
+            first.0 - start_direction.0 * before,
+            first.1 - start_direction.1 * before,
+        );
+        push(p, -before, start_direction);
+    }
+    for (i, (&p, &s)) in points.iter().zip(&lengths).enumerate() {
+        push(p, s, direction(points, i));
+    }
+    if after > 0.0 {
+        let p = Pt(
+            last.0 + end_direction.0 * after,
+            last.1 + end_direction.1 * after,
+        );
+        push(p, length + after, end_direction);
+    }
+    sketched
+}
+
+// Extra points so no two are further apart than spacing, for long lines to wobble
+fn resample(points: &[Pt<f64>], spacing: f64) -> Vec<Pt<f64>> {
+    let mut resampled = Vec::with_capacity(points.len());
+    resampled.extend(points.first().copied());
+    for pair in points.windows(2) {
+        let steps = (distance(pair[0], pair[1]) / spacing).ceil().max(1.0) as usize;
+        resampled.extend((1..=steps).map(|i| lerp(pair[0], pair[1], i as f64 / steps as f64)));
+    }
+    resampled
+}
+
+// Distance along the points to each of them
+fn arc_lengths(points: &[Pt<f64>]) -> Vec<f64> {
+    let mut total = 0.0;
+    let mut lengths = Vec::with_capacity(points.len());
+    lengths.push(0.0);
+    for pair in points.windows(2) {
+        total += distance(pair[0], pair[1]);
+        lengths.push(total);
+    }
+    lengths
+}
+
+// Direction of travel at a point, from its neighbours
+fn direction(points: &[Pt<f64>], i: usize) -> Pt<f64> {
+    let a = points[i.saturating_sub(1)];
+    let b = points[(i + 1).min(points.len() - 1)];
+    normalize(Pt(b.0 - a.0, b.1 - a.1))
+}
+
+struct Rng {
+    seed: u64,
+    position: usize,
+}
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self { seed, position: 0 }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.position += 1;
+        random(self.seed, self.position)
+    }
+
+    // 0 to 1
+    fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // -1 to 1
+    fn signed(&mut self) -> f64 {
+        self.unit() * 2.0 - 1.0
+    }
+}
+
+// Value noise, random values at whole numbers eased into each other
+struct Noise {
+    seed: u64,
+}
+
+impl Noise {
+    fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    // -1 to 1
+    fn at(&self, x: f64) -> f64 {
+        let cell = x.floor();
+        let t = x - cell;
+        let t = t * t * (3.0 - 2.0 * t);
+        let a = self.value(cell as i64);
+        let b = self.value(cell as i64 + 1);
+        a + (b - a) * t
+    }
+
+    fn value(&self, cell: i64) -> f64 {
+        Rng::new(self.seed ^ cell as u64).signed()
+    }
+}
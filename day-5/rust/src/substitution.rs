@@ -0,0 +1,58 @@
+// Ligatures and alternates, picked before glyphs are looked up.
+//
+// Where a ligature starts the longest one wins. Otherwise a contextual
+// alternate whose neighbours match is used, and failing that one of the
+// char's glyph and its variants is picked from the seed and the char's
+// position, so the same text with the same seed always comes out the same.
+// Without a seed there are no random picks.
+use crate::types::{Font, Glyph, Ligature};
+
+// The longest ligature the chars start with
+pub fn ligature<'a>(font: &'a Font, chars: &[char]) -> Option<&'a Ligature> {
+    font.ligatures
+        .iter()
+        .filter(|ligature| chars.starts_with(&ligature.chars))
+        .max_by_key(|ligature| ligature.chars.len())
+}
+
+// The form of a char's glyph to draw between previous and next. random picks
+// among the variants, see random(), without it the glyph itself is used.
+pub fn alternate<'a>(
+    font: &'a Font,
+    glyph: &'a Glyph,
+    previous: Option<char>,
+    next: Option<char>,
+    random: Option<u64>,
+) -> &'a Glyph {
+    let Some(alternates) = font.alternates.get(&glyph.ch) else {
+        return glyph;
+    };
+    if let Some(alternate) = alternates
+        .iter()
+        .find(|a| a.is_contextual() && a.matches(previous, next))
+    {
+        return &alternate.glyph;
+    }
+
+    let Some(random) = random else {
+        return glyph;
+    };
+    let variants: Vec<&Glyph> = std::iter::once(glyph)
+        .chain(
+            alternates
+                .iter()
+                .filter(|a| !a.is_contextual())
+                .map(|a| &a.glyph),
+        )
+        .collect();
+    variants[(random % variants.len() as u64) as usize]
+}
+
+// A random number for the char at position in the text, splitmix64
+pub fn random(seed: u64, position: usize) -> u64 {
+    let mut z = (seed ^ (position as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15))
+        .wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,241 @@
+// 2D affine transforms for laid out strokes.
+//
+// Rotation, uniform scaling, mirroring and translation keep circles circular,
+// so arcs stay arcs. Anything else (skew for italics, condensing one axis)
+// would turn an arc into an ellipse, those arcs are flattened to polylines.
+use std::ops::Mul;
+
+use crate::types::{Pt, Stroke};
+
+// x' = a*x + c*y + e
+// y' = b*x + d*y + f
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Transform {
+    pub fn identity() -> Self {
+        Self {
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+            d: 1.0,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+
+    pub fn translate(x: f64, y: f64) -> Self {
+        Self {
+            e: x,
+            f: y,
+            ..Self::identity()
+        }
+    }
+
+    pub fn scale(sx: f64, sy: f64) -> Self {
+        Self {
+            a: sx,
+            d: sy,
+            ..Self::identity()
+        }
+    }
+
+    // Counter-clockwise, in degrees
+    pub fn rotate(degrees: f64) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Self {
+            a: cos,
+            b: sin,
+            c: -sin,
+            d: cos,
+            ..Self::identity()
+        }
+    }
+
+    // Slants x by y, positive degrees lean to the right like italics
+    pub fn skew_x(degrees: f64) -> Self {
+        Self {
+            c: degrees.to_radians().tan(),
+            ..Self::identity()
+        }
+    }
+
+    pub fn skew_y(degrees: f64) -> Self {
+        Self {
+            b: degrees.to_radians().tan(),
+            ..Self::identity()
+        }
+    }
+
+    // Flip left to right
+    pub fn mirror_x() -> Self {
+        Self::scale(-1.0, 1.0)
+    }
+
+    // Flip upside down
+    pub fn mirror_y() -> Self {
+        Self::scale(1.0, -1.0)
+    }
+
+    // self first, then other
+    pub fn then(self, other: Transform) -> Transform {
+        Transform {
+            a: other.a * self.a + other.c * self.b,
+            b: other.b * self.a + other.d * self.b,
+            c: other.a * self.c + other.c * self.d,
+            d: other.b * self.c + other.d * self.d,
+            e: other.a * self.e + other.c * self.f + other.e,
+            f: other.b * self.e + other.d * self.f + other.f,
+        }
+    }
+
+    // The same transform applied around a point instead of the origin
+    pub fn around(self, p: Pt<f64>) -> Transform {
+        Transform::translate(-p.0, -p.1)
+            .then(self)
+            .then(Transform::translate(p.0, p.1))
+    }
+
+    pub fn inverse(&self) -> Option<Transform> {
+        let det = self.determinant();
+        if det.abs() < 1e-12 {
+            return None;
+        }
+        let (a, b, c, d) = (self.d / det, -self.b / det, -self.c / det, self.a / det);
+        Some(Transform {
+            a,
+            b,
+            c,
+            d,
+            e: -(a * self.e + c * self.f),
+            f: -(b * self.e + d * self.f),
+        })
+    }
+
+    pub fn determinant(&self) -> f64 {
+        self.a * self.d - self.b * self.c
+    }
+
+    // Scale factor when circles stay circles, i.e. no skew and the same
+    // scale on both axes
+    pub fn uniform_scale(&self) -> Option<f64> {
+        let sx = self.a.hypot(self.b);
+        let sy = self.c.hypot(self.d);
+        let dot = self.a * self.c + self.b * self.d;
+        let eps = 1e-9 * sx.max(sy);
+        (sx > 0.0 && (sx - sy).abs() <= eps && dot.abs() <= eps * sx).then_some(sx)
+    }
+
+    // How long a unit step along x comes out, the horizontal scale of a
+    // condensed or expanded transform. Skews and rotations leave it at 1.
+    pub fn x_scale(&self) -> f64 {
+        self.a.hypot(self.b)
+    }
+
+    // Largest stretch in any direction, used to keep flattening within tolerance
+    pub fn max_scale(&self) -> f64 {
+        // Largest singular value of the 2x2 part
+        let p = self.a * self.a + self.b * self.b;
+        let q = self.c * self.c + self.d * self.d;
+        let r = self.a * self.c + self.b * self.d;
+        let mean = (p + q) / 2.0;
+        let spread = (((p - q) / 2.0).powi(2) + r * r).sqrt();
+        (mean + spread).sqrt()
+    }
+
+    pub fn apply(&self, p: Pt<f64>) -> Pt<f64> {
+        Pt(
+            self.a * p.0 + self.c * p.1 + self.e,
+            self.b * p.0 + self.d * p.1 + self.f,
+        )
+    }
+
+    // Arcs that would become elliptical are flattened to within tolerance of
+    // the transformed arc
+    pub fn apply_stroke(&self, stroke: &Stroke<f64>, tolerance: f64) -> Stroke<f64> {
+        let pt = |p: &Pt<f64>| self.apply(*p);
+        match stroke {
+            Stroke::Line { from, to } => Stroke::Line {
+                from: pt(from),
+                to: pt(to),
+            },
+            Stroke::Arc {
+                center,
+                radius,
+                start,
+                end,
+            } => match self.uniform_scale() {
+                Some(scale) => {
+                    let rotation = self.b.atan2(self.a).to_degrees();
+                    // A mirror runs the angles backwards
+                    let (start, end) = if self.determinant() < 0.0 {
+                        (rotation - start, rotation - end)
+                    } else {
+                        (rotation + start, rotation + end)
+                    };
+                    Stroke::Arc {
+                        center: pt(center),
+                        radius: radius * scale,
+                        start,
+                        end,
+                    }
+                }
+                None => Stroke::Polyline {
+                    points: stroke
+                        .flatten(tolerance / self.max_scale().max(1e-12))
+                        .iter()
+                        .map(pt)
+                        .collect(),
+                },
+            },
+            Stroke::Quadratic { from, ctrl, to } => Stroke::Quadratic {
+                from: pt(from),
+                ctrl: pt(ctrl),
+                to: pt(to),
+            },
+            Stroke::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => Stroke::Cubic {
+                from: pt(from),
+                ctrl1: pt(ctrl1),
+                ctrl2: pt(ctrl2),
+                to: pt(to),
+            },
+            Stroke::Polyline { points } => Stroke::Polyline {
+                points: points.iter().map(pt).collect(),
+            },
+        }
+    }
+
+    pub fn apply_strokes(&self, strokes: &[Stroke<f64>], tolerance: f64) -> Vec<Stroke<f64>> {
+        strokes
+            .iter()
+            .map(|stroke| self.apply_stroke(stroke, tolerance))
+            .collect()
+    }
+}
+
+// `a * b` applies b first, like matrix multiplication
+impl Mul for Transform {
+    type Output = Transform;
+
+    fn mul(self, rhs: Transform) -> Transform {
+        rhs.then(self)
+    }
+}
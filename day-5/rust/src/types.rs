@@ -1,23 +1,140 @@
-#[derive(Debug)]
-pub struct Pt(pub i16, pub i16);
+use std::collections::{BTreeMap, HashMap};
 
-#[derive(Debug)]
-pub enum Stroke {
+use serde::{Deserialize, Serialize};
+
+// Glyph definitions use i16 in the 0-100 box, laid out strokes use f64
+// Serialized as [x, y]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Pt<T = i16>(pub T, pub T);
+
+// Serialized with a "type" field, e.g. {"type": "line", "from": [0, 0], "to": [100, 100]}
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Stroke<T = i16> {
     Line {
-        from: Pt,
-        to: Pt,
+        from: Pt<T>,
+        to: Pt<T>,
     },
     Arc {
-        center: Pt,
-        radius: i16,
-        start: i16,
-        end: i16,
+        center: Pt<T>,
+        radius: T,
+        start: T,
+        end: T,
+    },
+    Quadratic {
+        from: Pt<T>,
+        ctrl: Pt<T>,
+        to: Pt<T>,
+    },
+    Cubic {
+        from: Pt<T>,
+        ctrl1: Pt<T>,
+        ctrl2: Pt<T>,
+        to: Pt<T>,
     },
+    Polyline {
+        points: Vec<Pt<T>>,
+    },
+}
+
+// Points where combining marks attach. A base glyph's anchor is where the
+// mark goes, a mark's anchor is the point of the mark that lands on it.
+// Entry and exit are where the pen comes in and leaves in cursive layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Anchor {
+    Top,
+    Bottom,
+    AboveRight,
+    Entry,
+    Exit,
 }
 
-#[derive(Debug)]
+impl Anchor {
+    pub fn is_cursive(self) -> bool {
+        matches!(self, Anchor::Entry | Anchor::Exit)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Glyph {
     pub ch: char,
     pub strokes: Vec<Stroke>,
     pub width_restriction: Option<u8>, // 0-100 percentage of width
-}
\ No newline at end of file
+    pub anchors: BTreeMap<Anchor, Pt>, // missing ones are derived from the strokes
+}
+
+// Vertical font metrics in glyph units, y up. Glyph coordinates are relative
+// to these lines, so descenders go below the baseline and ascenders above x_height.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FontMetrics {
+    pub baseline: i16,
+    pub x_height: i16,
+    pub cap_height: i16,
+    pub ascender: i16,
+    pub descender: i16, // below the baseline, usually negative
+    pub line_gap: i16,  // extra space between one line's descender and the next line's ascender
+}
+
+impl Default for FontMetrics {
+    // Everything in the original 0-100 box
+    fn default() -> Self {
+        Self {
+            baseline: 0,
+            x_height: 100,
+            cap_height: 100,
+            ascender: 100,
+            descender: 0,
+            line_gap: 25,
+        }
+    }
+}
+
+impl FontMetrics {
+    // Baseline to baseline distance, in i32 so extreme metrics can't overflow
+    pub fn line_height(&self) -> i32 {
+        self.ascender as i32 - self.descender as i32 + self.line_gap as i32
+    }
+}
+
+// A glyph drawn in place of a run of chars, e.g. fi. It may use the width of
+// all of them, x goes up to 100 for every char.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ligature {
+    pub chars: Vec<char>,
+    pub glyph: Glyph,
+}
+
+// Another form of a char's glyph. With neighbours given it's used when they
+// match, without any it's a variant picked at random.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alternate {
+    pub glyph: Glyph,
+    pub before: Vec<char>, // one of these has to come next
+    pub after: Vec<char>,  // one of these has to come before
+}
+
+impl Alternate {
+    pub fn is_contextual(&self) -> bool {
+        !self.before.is_empty() || !self.after.is_empty()
+    }
+
+    pub fn matches(&self, previous: Option<char>, next: Option<char>) -> bool {
+        let fits = |chars: &[char], ch: Option<char>| {
+            chars.is_empty() || ch.is_some_and(|ch| chars.contains(&ch))
+        };
+        fits(&self.after, previous) && fits(&self.before, next)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Font {
+    pub metrics: FontMetrics,
+    pub glyphs: HashMap<char, Glyph>,
+    pub ligatures: Vec<Ligature>,
+    pub alternates: HashMap<char, Vec<Alternate>>, // in the order they were defined
+    // Spacing for pairs in glyph units, negative pulls closer. These win over
+    // automatic kerning.
+    pub kerning: HashMap<(char, char), i16>,
+}
@@ -0,0 +1,148 @@
+use std::process::Command;
+
+fn day_5(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_day-5"))
+        .args(args)
+        .output()
+        .expect("failed to run day-5")
+}
+
+#[test]
+fn renders_svg_to_stdout() {
+    let output = day_5(&[
+        "render",
+        "--text",
+        "hi",
+        "--color",
+        "#ff0000",
+        "--page",
+        "300x200",
+        "--line-width",
+        "2",
+    ]);
+    assert!(output.status.success());
+    let svg = String::from_utf8(output.stdout).unwrap();
+    assert!(svg.contains(r#"<svg width="300" height="200""#));
+    assert!(svg.contains(r#"stroke="rgb(255,0,0)" stroke-width="2""#));
+    assert!(svg.contains(r#"id="letter-1-i""#));
+}
+
+#[test]
+fn picks_the_format_from_the_output_file() {
+    let dir = std::env::temp_dir().join(format!("day-5-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let out = dir.join("logo.gcode");
+
+    let output = day_5(&[
+        "render",
+        "--text",
+        "genurary",
+        "--scale",
+        "0.5",
+        "--spacing",
+        "1.5",
+        "--x",
+        "10",
+        "--y",
+        "50",
+        "--optimize",
+        "--out",
+        out.to_str().unwrap(),
+    ]);
+    assert!(output.status.success());
+    let gcode = std::fs::read_to_string(&out).unwrap();
+    assert!(gcode.contains("G21"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Pen-up travel"));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn plotters_fit_the_page_by_default() {
+    for format in ["gcode", "hpgl"] {
+        let output = day_5(&["render", "--text", "genurary", "--format", format]);
+        assert!(output.status.success());
+        assert!(output.stderr.is_empty(), "{format} clipped");
+        let out = String::from_utf8(output.stdout).unwrap();
+        assert!(!out.contains("Clipped"));
+        let draws = out
+            .lines()
+            .filter(|l| l.starts_with("G1") || l.starts_with("G2") || l.starts_with("G3"))
+            .count()
+            + out.matches("PD").count();
+        assert!(draws > 15, "{format} drew {draws} moves");
+    }
+}
+
+#[test]
+fn hpgl_clips_to_the_page() {
+    let output = day_5(&[
+        "render", "--text", "genurary", "--format", "hpgl", "--page", "100x100",
+    ]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("edge of the paper"));
+}
+
+#[test]
+fn renders_png_previews() {
+    let output = day_5(&[
+        "render",
+        "--text",
+        "genurary",
+        "--format",
+        "png",
+        "--page",
+        "600x200",
+        "--dpi",
+        "144",
+        "--background",
+        "none",
+    ]);
+    assert!(output.status.success());
+    let png = output.stdout;
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    // IHDR width and height
+    assert_eq!(&png[16..24], &[0, 0, 4, 176, 0, 0, 1, 144]);
+}
+
+#[test]
+fn sketches_the_same_from_the_same_seed() {
+    let gcode = |extra: &[&str]| {
+        let mut args = vec![
+            "render", "--text", "hi", "--format", "gcode", "--page", "200x100",
+        ];
+        args.extend(extra);
+        let output = day_5(&args);
+        assert!(output.status.success());
+        output.stdout
+    };
+    let sketched = gcode(&["--sketch", "1", "--seed", "4"]);
+    assert_eq!(sketched, gcode(&["--sketch", "1", "--seed", "4"]));
+    assert_ne!(sketched, gcode(&["--sketch", "1", "--seed", "5"]));
+    assert_ne!(sketched, gcode(&[]));
+}
+
+#[test]
+fn reports_bad_arguments() {
+    let output = day_5(&["render", "--text", "a", "--color", "red"]);
+    assert!(!output.status.success());
+
+    // Six bytes but not six hex digits, an error rather than a panic
+    for flag in ["--color", "--background"] {
+        let args = [
+            "render",
+            "--text",
+            "a",
+            "--format",
+            "png",
+            flag,
+            "#a\u{e9}bbb",
+        ];
+        let output = day_5(&args);
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("expected #rrggbb"));
+    }
+
+    let output = day_5(&["render", "--text", "a", "--font", "missing.toml"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("error: "));
+}
@@ -0,0 +1,132 @@
+use day_5::clip::ClipArea;
+use day_5::drawing::{Drawing, DrawingOptions};
+use day_5::renderers::{GcodeConfig, GcodeDrawing};
+use day_5::types::{Pt, Stroke};
+
+fn close(a: Pt<f64>, b: Pt<f64>) -> bool {
+    (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9
+}
+
+fn line(x1: f64, y1: f64, x2: f64, y2: f64) -> Stroke<f64> {
+    Stroke::Line {
+        from: Pt(x1, y1),
+        to: Pt(x2, y2),
+    }
+}
+
+#[test]
+fn splits_lines_at_the_boundary() {
+    let area = ClipArea::rect(0.0, 0.0, 100.0, 50.0);
+
+    let inside = line(10.0, 10.0, 90.0, 40.0);
+    assert_eq!(area.clip_stroke(&inside, 0.1), vec![inside.clone()]);
+    assert!(area
+        .clip_stroke(&line(-10.0, 60.0, 120.0, 60.0), 0.1)
+        .is_empty());
+
+    // Crosses the right edge, the part on the bed keeps its direction
+    let pieces = area.clip_stroke(&line(50.0, 25.0, 150.0, 25.0), 0.1);
+    assert_eq!(pieces.len(), 1);
+    assert!(close(pieces[0].start_point(), Pt(50.0, 25.0)));
+    assert!(close(pieces[0].end_point(), Pt(100.0, 25.0)));
+
+    // Through the area from outside to outside
+    let pieces = area.clip_stroke(&line(-50.0, -25.0, 150.0, 75.0), 0.1);
+    assert!(close(pieces[0].start_point(), Pt(0.0, 0.0)));
+    assert!(close(pieces[0].end_point(), Pt(100.0, 50.0)));
+}
+
+#[test]
+fn arcs_stay_arcs() {
+    let area = ClipArea::rect(0.0, 0.0, 100.0, 100.0);
+    // Circle around the corner, only the quarter on the bed is left
+    let circle = Stroke::Arc {
+        center: Pt(0.0, 0.0),
+        radius: 20.0,
+        start: 180.0,
+        end: -180.0,
+    };
+    let pieces = area.clip_stroke(&circle, 0.1);
+    assert_eq!(pieces.len(), 1);
+    let Stroke::Arc { start, end, .. } = pieces[0] else {
+        panic!("expected an arc, got {:?}", pieces[0]);
+    };
+    // Clockwise from 90 to 0 degrees
+    assert!((start - 90.0).abs() < 1e-9 && end.abs() < 1e-9);
+
+    // Crosses the top edge twice, leaving two arcs
+    let arc = Stroke::Arc {
+        center: Pt(50.0, 90.0),
+        radius: 20.0,
+        start: 0.0,
+        end: 360.0,
+    };
+    let pieces = area.clip_stroke(&arc, 0.1);
+    assert_eq!(pieces.len(), 2);
+    assert!(pieces.iter().all(|p| matches!(p, Stroke::Arc { .. })));
+    let drawn: f64 = pieces.iter().map(Stroke::length).sum();
+    let expected = 20.0 * (2.0 * std::f64::consts::PI - 2.0 * (10.0f64 / 20.0).acos());
+    assert!((drawn - expected).abs() < 1e-6);
+}
+
+#[test]
+fn clips_to_concave_polygons_and_reports() {
+    // L shaped work area, the notch is the top right corner
+    let area = ClipArea::polygon(&[
+        Pt(0.0, 0.0),
+        Pt(100.0, 0.0),
+        Pt(100.0, 50.0),
+        Pt(50.0, 50.0),
+        Pt(50.0, 100.0),
+        Pt(0.0, 100.0),
+    ]);
+    let strokes = [
+        line(25.0, 75.0, 100.0, 25.0),
+        line(75.0, 75.0, 90.0, 90.0),
+        Stroke::Cubic {
+            from: Pt(10.0, 10.0),
+            ctrl1: Pt(10.0, 200.0),
+            ctrl2: Pt(40.0, 200.0),
+            to: Pt(40.0, 10.0),
+        },
+    ];
+
+    let clipped = area.clip(&strokes, 0.1);
+    let report = clipped.report;
+    assert_eq!((report.strokes, report.split, report.removed), (3, 2, 1));
+    // The diagonal goes out through the notch and back in
+    assert!(close(clipped.strokes[0].end_point(), Pt(50.0, 175.0 / 3.0)));
+    assert!(close(clipped.strokes[1].start_point(), Pt(62.5, 50.0)));
+    // The curve leaves through the top and comes back as two polylines
+    assert!(matches!(clipped.strokes[2], Stroke::Polyline { .. }));
+    assert!(matches!(clipped.strokes[3], Stroke::Polyline { .. }));
+    assert_eq!(clipped.strokes.len(), 4);
+    let removed = 2.0f64.sqrt() * 15.0;
+    assert!(report.clipped_length() > removed);
+    assert!(clipped
+        .strokes
+        .iter()
+        .flat_map(|s| s.flatten(0.1))
+        .all(|p| area.contains(p)));
+}
+
+#[test]
+fn gcode_clips_to_the_bed_instead_of_clamping() {
+    let config = GcodeConfig {
+        bed_width: 100.0,
+        bed_height: 100.0,
+        origin_x: 0.0,
+        origin_y: 0.0,
+        ..Default::default()
+    };
+    let mut gcode = GcodeDrawing::new(DrawingOptions::default(), config);
+    gcode.line(50.0, 50.0, 150.0, 150.0);
+    gcode.line(120.0, 10.0, 150.0, 10.0);
+    let output = gcode.render();
+
+    // Along the original line up to the corner, not bent onto the edge
+    assert!(output.contains("G1 X100 Y100"));
+    assert!(!output.contains("X120") && !output.contains("Y10 "));
+    assert_eq!(gcode.clip_report().removed, 1);
+    assert!(output.contains("; Clipped 2 strokes to the work area"));
+}
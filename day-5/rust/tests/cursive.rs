@@ -0,0 +1,80 @@
+use day_5::geometry::distance;
+use day_5::glyph_file::parse_font;
+use day_5::layout::{LayoutResult, TextLayout};
+use day_5::letters::define_font;
+use day_5::optimize::travel_distance;
+use day_5::types::{Font, Pt, Stroke};
+
+fn layout(font: &Font, text: &str, cursive: bool) -> LayoutResult {
+    TextLayout::new(0.0, 0.0, 1.0, 1.0)
+        .cursive(cursive)
+        .layout(font, text)
+}
+
+fn close(a: Pt<f64>, b: Pt<f64>) -> bool {
+    distance(a, b) < 1e-9
+}
+
+#[test]
+fn joins_the_letters_of_a_word() {
+    let font = define_font();
+    let plain = layout(&font, "minimum", false);
+    let joined = layout(&font, "minimum", true);
+
+    // One join between every pair, each running into the next letter's first stroke
+    assert_eq!(joined.strokes().count(), plain.strokes().count() + 6);
+    for pair in joined.glyphs.windows(2) {
+        let join = pair[0].strokes.last().unwrap();
+        assert!(matches!(join, Stroke::Cubic { .. }));
+        assert!(close(join.end_point(), pair[1].strokes[0].start_point()));
+    }
+
+    let travel = |result: &LayoutResult| travel_distance(result.strokes(), Pt(0.0, 0.0), 0.01);
+    assert!(travel(&joined) < travel(&plain) / 2.0);
+}
+
+#[test]
+fn spaces_and_lines_break_the_chain() {
+    let font = define_font();
+    let plain = layout(&font, "nu nu\nnu", false);
+    let joined = layout(&font, "nu nu\nnu", true);
+    assert_eq!(joined.strokes().count(), plain.strokes().count() + 3);
+
+    // Nothing is added without the mode
+    let default = TextLayout::new(0.0, 0.0, 1.0, 1.0).layout(&font, "nu nu\nnu");
+    assert_eq!(
+        default.strokes().collect::<Vec<_>>(),
+        plain.strokes().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn joins_run_between_the_anchors() {
+    let font = parse_font(
+        "
+        'a' {
+            line (0, 0) -> (0, 100)
+            line (100, 0) -> (0, 0)
+            anchor entry (0, 100)
+            anchor exit (100, 0)
+        }
+        'b' {
+            line (0, 50) -> (100, 50)
+            anchor entry (0, 50)
+        }
+        ",
+    )
+    .unwrap();
+    let result = layout(&font, "ab", true);
+    let a = &result.glyphs[0];
+
+    // The strokes of a go from its entry to its exit, the second one turned around
+    assert!(close(a.strokes[0].start_point(), Pt(0.0, 40.0)));
+    assert!(close(a.strokes[1].start_point(), Pt(0.0, 0.0)));
+    assert!(close(a.strokes[1].end_point(), Pt(40.0, 0.0)));
+
+    // b starts one 40 unit glyph and 20 units of spacing along
+    let join = &a.strokes[2];
+    assert!(close(join.start_point(), Pt(40.0, 0.0)));
+    assert!(close(join.end_point(), Pt(60.0, 20.0)));
+}
@@ -0,0 +1,148 @@
+use day_5::font_data::{FontDataError, FontDocument};
+use day_5::letters::define_font;
+use day_5::types::{FontMetrics, Pt, Stroke};
+
+fn builtin() -> FontDocument {
+    FontDocument {
+        name: Some("genurary".to_string()),
+        author: Some("day-5".to_string()),
+        ..FontDocument::from_font(&define_font())
+    }
+}
+
+#[test]
+fn json_round_trip_keeps_everything() {
+    let doc = builtin();
+    let json = doc.to_json().unwrap();
+    let parsed = FontDocument::from_json(&json).unwrap();
+    assert_eq!(parsed, doc);
+    assert_eq!(parsed.to_font(), define_font());
+}
+
+#[test]
+fn toml_round_trip_keeps_everything() {
+    let doc = builtin();
+    let toml = doc.to_toml().unwrap();
+    let parsed = FontDocument::from_toml(&toml).unwrap();
+    assert_eq!(parsed, doc);
+    assert_eq!(parsed.to_font(), define_font());
+}
+
+#[test]
+fn reads_the_documented_schema() {
+    let doc = FontDocument::from_json(
+        r#"{
+            "format": 1,
+            "metrics": { "descender": -40 },
+            "glyphs": {
+                "c": {
+                    "strokes": [
+                        { "type": "arc", "center": [50, 50], "radius": 50, "start": 45, "end": 315 },
+                        { "type": "polyline", "points": [[0, 0], [50, 100], [100, 0]] }
+                    ],
+                    "width_restriction": 70
+                },
+                " ": {}
+            }
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        doc.metrics,
+        FontMetrics {
+            descender: -40,
+            ..FontMetrics::default()
+        }
+    );
+    let font = doc.to_font();
+    let c = &font.glyphs[&'c'];
+    assert_eq!(c.ch, 'c');
+    assert_eq!(c.width_restriction, Some(70));
+    assert_eq!(
+        c.strokes[0],
+        Stroke::Arc {
+            center: Pt(50, 50),
+            radius: 50,
+            start: 45,
+            end: 315
+        }
+    );
+    assert!(font.glyphs[&' '].strokes.is_empty());
+}
+
+#[test]
+fn rejects_other_format_versions() {
+    let err = FontDocument::from_toml("format = 2\n").unwrap_err();
+    assert!(matches!(err, FontDataError::UnsupportedFormat(2)));
+}
+
+#[test]
+fn checks_values_like_the_parser() {
+    let invalid =
+        |json: &str| match FontDocument::from_json(&format!(r#"{{ "format": 1, {json} }}"#)) {
+            Err(FontDataError::Invalid(message)) => message,
+            other => panic!("expected an invalid font for {json}, got {other:?}"),
+        };
+    let glyph = |stroke: &str| format!(r#""glyphs": {{ "a": {{ "strokes": [{stroke}] }} }}"#);
+
+    let message = invalid(&glyph(
+        r#"{ "type": "arc", "center": [50, 50], "radius": 0, "start": 0, "end": 90 }"#,
+    ));
+    assert_eq!(message, "glyph 'a': radius must be positive, found `0`");
+    let message = invalid(&glyph(r#"{ "type": "polyline", "points": [[0, 0]] }"#));
+    assert!(message.contains("at least two points"), "{message}");
+    let message = invalid(&glyph(
+        r#"{ "type": "line", "from": [0, 0], "to": [101, 0] }"#,
+    ));
+    assert!(message.contains("outside the 0-100 glyph box"), "{message}");
+    let message = invalid(&glyph(
+        r#"{ "type": "line", "from": [0, 0], "to": [0, -1] }"#,
+    ));
+    assert!(
+        message.contains("descender-ascender range 0..=100"),
+        "{message}"
+    );
+
+    let message = invalid(r#""glyphs": { "a": { "width_restriction": 120 } }"#);
+    assert!(message.contains("between 0 and 100"), "{message}");
+    let message = invalid(r#""metrics": { "x_height": 120 }"#);
+    assert_eq!(
+        message,
+        "cap_height (100) must be at or above x_height (120)"
+    );
+    let message = invalid(r#""metrics": { "line_gap": -1 }"#);
+    assert!(message.contains("line_gap"), "{message}");
+    let message = invalid(r#""kerning": { "ry": 150 }"#);
+    assert!(message.contains("between -100 and 100"), "{message}");
+
+    // Ligatures get a glyph width per char
+    let ligature = r#""ligatures": [{ "chars": "fi", "glyph": { "strokes": [
+        { "type": "line", "from": [0, 0], "to": [200, 0] }
+    ] } }]"#;
+    FontDocument::from_json(&format!(r#"{{ "format": 1, {ligature} }}"#)).unwrap();
+    let message = invalid(&ligature.replace("200", "201"));
+    assert!(
+        message.starts_with("ligature \"fi\": x coordinate"),
+        "{message}"
+    );
+}
+
+#[test]
+fn glyph_keys_are_single_characters() {
+    let err = FontDocument::from_json(r#"{ "format": 1, "glyphs": { "ab": {} } }"#).unwrap_err();
+    assert!(err.to_string().contains("single character"), "{err}");
+}
+
+#[test]
+fn kerning_keys_are_pairs() {
+    let mut font = define_font();
+    font.kerning.insert(('r', 'y'), -10);
+    let doc = FontDocument::from_font(&font);
+    let toml = doc.to_toml().unwrap();
+    assert!(toml.contains("ry = -10"), "{toml}");
+    assert_eq!(FontDocument::from_toml(&toml).unwrap().to_font(), font);
+
+    let err = FontDocument::from_json(r#"{ "format": 1, "kerning": { "r": 5 } }"#).unwrap_err();
+    assert!(err.to_string().contains("two characters"), "{err}");
+}
@@ -0,0 +1,95 @@
+use day_5::drawing::{Drawing, DrawingOptions};
+use day_5::renderers::{GcodeConfig, GcodeDrawing};
+
+// No origin offset, so coordinates come out as given
+fn plain() -> GcodeConfig {
+    GcodeConfig {
+        origin_x: 0.0,
+        origin_y: 0.0,
+        ..Default::default()
+    }
+}
+
+// The commands after the six line header
+fn commands(gcode: &mut GcodeDrawing) -> Vec<String> {
+    gcode.render().lines().skip(6).map(String::from).collect()
+}
+
+#[test]
+fn lifts_the_pen_only_between_separate_strokes() {
+    let mut gcode = GcodeDrawing::new(DrawingOptions::default(), plain());
+    gcode.line(0.0, 0.0, 10.0, 0.0);
+    gcode.line(10.0, 0.0, 10.0, 10.0); // continues the first
+    gcode.line(50.0, 50.0, 60.0, 50.0);
+    assert_eq!(
+        commands(&mut gcode),
+        [
+            "G0 X0 Y0",
+            "M280 P0 S30 ; Pen down",
+            "G4 P300 ; Wait for pen to move",
+            "G1 X10 Y0 F1000",
+            "G1 X10 Y10 F1000",
+            "M280 P0 S90 ; Pen up",
+            "G4 P300 ; Wait for pen to move",
+            "G0 X50 Y50",
+            "M280 P0 S30 ; Pen down",
+            "G4 P300 ; Wait for pen to move",
+            "G1 X60 Y50 F1000",
+            "M280 P0 S90 ; Pen up",
+            "G4 P300 ; Wait for pen to move",
+            "G0 X0 Y0 ; Return to origin",
+            "M84 ; Disable motors",
+        ]
+    );
+
+    // Nothing drawn, the pen stays up from the header
+    let mut gcode = GcodeDrawing::new(DrawingOptions::default(), plain());
+    assert_eq!(
+        commands(&mut gcode),
+        ["G0 X0 Y0 ; Return to origin", "M84 ; Disable motors"]
+    );
+}
+
+#[test]
+fn splits_arcs_at_half_turns() {
+    let mut gcode = GcodeDrawing::new(DrawingOptions::default(), plain());
+    // A full circle anticlockwise, two G3 halves
+    gcode.arc(50.0, 50.0, 10.0, 0.0, 360.0);
+    // Half a turn clockwise is still one G2
+    gcode.arc(100.0, 50.0, 10.0, 90.0, -90.0);
+    let arcs: Vec<String> = commands(&mut gcode)
+        .into_iter()
+        .filter(|c| c.starts_with("G0") || c.starts_with("G2") || c.starts_with("G3"))
+        .collect();
+    assert_eq!(
+        arcs,
+        [
+            "G0 X60 Y50",
+            "G3 X40 Y50 I-10 J0 F1000",
+            "G3 X60 Y50 I10 J0 F1000",
+            "G0 X100 Y60",
+            "G2 X100 Y40 I0 J-10 F1000",
+            "G0 X0 Y0 ; Return to origin",
+        ]
+    );
+
+    // Three quarters of a turn in two equal parts
+    let mut gcode = GcodeDrawing::new(DrawingOptions::default(), plain());
+    gcode.arc(50.0, 50.0, 10.0, 0.0, 270.0);
+    let commands = commands(&mut gcode);
+    let parts: Vec<_> = commands.iter().filter(|c| c.starts_with("G3")).collect();
+    assert_eq!(parts.len(), 2);
+    assert!(parts[0].starts_with("G3 X42.929 Y57.071 I-10 J0"));
+    assert!(parts[1].starts_with("G3 X50 Y40 I7.071 J-7.071"));
+}
+
+#[test]
+fn leaves_out_arcs_without_a_sweep() {
+    // Ending where it starts would be a full circle to the plotter
+    let mut gcode = GcodeDrawing::new(DrawingOptions::default(), plain());
+    gcode.arc(50.0, 50.0, 10.0, 90.0, 90.0);
+    assert_eq!(
+        commands(&mut gcode),
+        ["G0 X0 Y0 ; Return to origin", "M84 ; Disable motors"]
+    );
+}
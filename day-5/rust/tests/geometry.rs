@@ -0,0 +1,118 @@
+use day_5::geometry::{distance, simplify};
+use day_5::types::{Pt, Stroke};
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{a} != {b}");
+}
+
+#[test]
+fn curve_bounds_include_extrema() {
+    // Peak of the curve is at t = 0.5, y = 75
+    let cubic = Stroke::Cubic {
+        from: Pt(0.0, 0.0),
+        ctrl1: Pt(0.0, 100.0),
+        ctrl2: Pt(100.0, 100.0),
+        to: Pt(100.0, 0.0),
+    };
+    let bounds = cubic.bounds();
+    assert_close(bounds.max_y, 75.0);
+    assert_close(bounds.min_x, 0.0);
+    assert_close(bounds.max_x, 100.0);
+
+    let quad = Stroke::Quadratic {
+        from: Pt(0.0, 0.0),
+        ctrl: Pt(50.0, 100.0),
+        to: Pt(100.0, 0.0),
+    };
+    assert_close(quad.bounds().max_y, 50.0);
+}
+
+#[test]
+fn flattened_curves_stay_within_tolerance() {
+    let quad = Stroke::Quadratic {
+        from: Pt(0.0, 0.0),
+        ctrl: Pt(50.0, 100.0),
+        to: Pt(100.0, 0.0),
+    };
+    let points = quad.flatten(0.1);
+    assert_eq!(points.first(), Some(&Pt(0.0, 0.0)));
+    assert_eq!(points.last(), Some(&Pt(100.0, 0.0)));
+
+    // Midpoint of every chord is close to the curve at the matching parameter
+    let n = (points.len() - 1) as f64;
+    for (i, pair) in points.windows(2).enumerate() {
+        let mid = Pt((pair[0].0 + pair[1].0) / 2.0, (pair[0].1 + pair[1].1) / 2.0);
+        let on_curve = quad.point_at((i as f64 + 0.5) / n);
+        assert!(distance(mid, on_curve) <= 0.1);
+    }
+}
+
+#[test]
+fn arcs_sweep_from_start_to_end() {
+    // 'm' hump: clockwise over the top
+    let hump = Stroke::Arc {
+        center: Pt(0.0, 0.0),
+        radius: 10.0,
+        start: 180.0,
+        end: 0.0,
+    };
+    assert_close(hump.point_at(0.5).1, 10.0);
+    assert_close(hump.tangent_at(0.5).0, 1.0);
+    assert_close(hump.length(), 10.0 * std::f64::consts::PI);
+
+    // 'g' tail: counter-clockwise through 0
+    let tail = Stroke::Arc {
+        center: Pt(0.0, 0.0),
+        radius: 10.0,
+        start: 240.0,
+        end: 390.0,
+    };
+    assert_close(tail.sweep(), 150.0);
+    let bounds = tail.bounds();
+    assert_close(bounds.max_x, 10.0);
+    assert_close(bounds.min_y, -10.0);
+    assert_close(bounds.min_x, -5.0);
+    assert_close(bounds.max_y, 5.0);
+}
+
+#[test]
+fn ink_bounds_add_half_the_line_width() {
+    let line = Stroke::Line {
+        from: Pt(0.0, 0.0),
+        to: Pt(10.0, 0.0),
+    };
+    let ink = line.ink_bounds(2.0);
+    assert_close(ink.min_x, -1.0);
+    assert_close(ink.max_y, 1.0);
+}
+
+#[test]
+fn curve_length_matches_flattened_length() {
+    let cubic = Stroke::Cubic {
+        from: Pt(0.0, 0.0),
+        ctrl1: Pt(0.0, 100.0),
+        ctrl2: Pt(100.0, 100.0),
+        to: Pt(100.0, 0.0),
+    };
+    let flattened: f64 = cubic
+        .flatten(1e-4)
+        .windows(2)
+        .map(|p| distance(p[0], p[1]))
+        .sum();
+    assert!((cubic.length() - flattened).abs() < 1e-3);
+}
+
+#[test]
+fn simplify_drops_collinear_points() {
+    let points = vec![
+        Pt(0.0, 0.0),
+        Pt(1.0, 0.01),
+        Pt(2.0, 0.0),
+        Pt(3.0, 5.0),
+        Pt(4.0, 0.0),
+    ];
+    assert_eq!(
+        simplify(&points, 0.1),
+        vec![Pt(0.0, 0.0), Pt(2.0, 0.0), Pt(3.0, 5.0), Pt(4.0, 0.0)]
+    );
+}
@@ -0,0 +1,199 @@
+use day_5::glyph_file::{parse_font, parse_glyphs};
+use day_5::layout::TextLayout;
+use day_5::letters::define_font;
+use day_5::types::{Anchor, FontMetrics, Pt, Stroke};
+
+// The body of the glyphs! invocation in letters.rs is valid .glyphs source
+fn builtin_source() -> &'static str {
+    let source = include_str!("../src/letters.rs");
+    let start = source.find("    glyphs! {").expect("glyphs! invocation") + "    glyphs! {".len();
+    let end = source.rfind("    }\n}").expect("end of glyphs! invocation");
+    &source[start..end]
+}
+
+#[test]
+fn parses_the_builtin_glyphs() {
+    let parsed = parse_font(builtin_source()).unwrap();
+    assert_eq!(parsed, define_font());
+}
+
+#[test]
+fn parses_metrics_with_defaults() {
+    let font = parse_font("metrics { descender (-40) ascender (130) }\n'a' { }").unwrap();
+    assert_eq!(
+        font.metrics,
+        FontMetrics {
+            descender: -40,
+            ascender: 130,
+            ..FontMetrics::default()
+        }
+    );
+    assert_eq!(font.glyphs.len(), 1);
+
+    let err = parse_font("metrics { x_height (1) }\nmetrics { }").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 1));
+    assert!(parse_font("metrics { descent (-40) }").is_err());
+}
+
+#[test]
+fn parses_strokes_and_width() {
+    let glyphs = parse_glyphs(
+        "// a small font\n\
+         'x' {\n\
+             line (0, 0) -> (100, 100)\n\
+             arc (50, 50) radius (25) from (180) -> (-90)\n\
+             restrict_width (40)\n\
+         }\n\
+         '\\'' { }\n",
+    )
+    .unwrap();
+
+    let x = &glyphs[&'x'];
+    assert_eq!(
+        x.strokes,
+        vec![
+            Stroke::Line {
+                from: Pt(0, 0),
+                to: Pt(100, 100)
+            },
+            Stroke::Arc {
+                center: Pt(50, 50),
+                radius: 25,
+                start: 180,
+                end: -90
+            },
+        ]
+    );
+    assert_eq!(x.width_restriction, Some(40));
+    assert!(glyphs[&'\''].strokes.is_empty());
+}
+
+#[test]
+fn parses_curves_and_polylines() {
+    let glyphs = parse_glyphs(
+        "'s' {\n\
+             quad (0, 0) via (50, 100) -> (100, 0)\n\
+             cubic (0, 0) via (0, 100) (100, 100) -> (100, 0)\n\
+             poly (0, 0) -> (50, 50) -> (100, 0)\n\
+         }",
+    )
+    .unwrap();
+
+    assert_eq!(
+        glyphs[&'s'].strokes,
+        vec![
+            Stroke::Quadratic {
+                from: Pt(0, 0),
+                ctrl: Pt(50, 100),
+                to: Pt(100, 0)
+            },
+            Stroke::Cubic {
+                from: Pt(0, 0),
+                ctrl1: Pt(0, 100),
+                ctrl2: Pt(100, 100),
+                to: Pt(100, 0)
+            },
+            Stroke::Polyline {
+                points: vec![Pt(0, 0), Pt(50, 50), Pt(100, 0)]
+            },
+        ]
+    );
+}
+
+#[test]
+fn reports_line_and_column_of_errors() {
+    let err = parse_glyphs("'a' {\n    line (0, 0) -> (100 100)\n}").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 25));
+
+    let err = parse_glyphs("'a' {\n    curve (0, 0)\n}").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 5));
+
+    let err = parse_glyphs("'a' { }\n'a' { }").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 1));
+    assert!(err.message.contains("more than once"));
+
+    let err = parse_glyphs("'a' {\n    line (0, 0)").unwrap_err();
+    assert_eq!(err.line, 2);
+    assert!(err.message.contains("end of file"));
+}
+
+#[test]
+fn parses_anchors() {
+    let glyphs =
+        parse_glyphs("'\\u{301}' {\n line (45, 15) -> (65, 40)\n anchor top (50, 0)\n}").unwrap();
+    let acute = &glyphs[&'\u{301}'];
+    assert_eq!(acute.anchors.get(&Anchor::Top), Some(&Pt(50, 0)));
+
+    let err = parse_glyphs("'o' {\n anchor middle (50, 50)\n}").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 9));
+    assert!(parse_glyphs("'o' { anchor top (50, 100) anchor top (50, 90) }").is_err());
+}
+
+#[test]
+fn parses_ligatures_and_alternates() {
+    let font = parse_font(
+        "'r' { line (0, 0) -> (0, 100) }\n\
+         ligature 'f' 'i' { line (25, 0) -> (150, 0) }\n\
+         alternate 'r' before ('n', 'm') after ('o') { restrict_width (60) }\n\
+         alternate 'r' { }\n",
+    )
+    .unwrap();
+
+    assert_eq!(font.ligatures.len(), 1);
+    assert_eq!(font.ligatures[0].chars, vec!['f', 'i']);
+    assert_eq!(font.ligatures[0].glyph.ch, 'f');
+
+    let alternates = &font.alternates[&'r'];
+    assert_eq!(alternates[0].before, vec!['n', 'm']);
+    assert_eq!(alternates[0].after, vec!['o']);
+    assert_eq!(alternates[0].glyph.width_restriction, Some(60));
+    assert!(!alternates[1].is_contextual());
+
+    let err = parse_font("ligature 'f' { }").unwrap_err();
+    assert!(err.message.contains("at least two"));
+    let err = parse_font("ligature 'f' 'i' { }\nligature 'f' 'i' { }").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 1));
+    let err = parse_font("alternate 'r' { }").unwrap_err();
+    assert!(err.message.contains("no glyph"));
+    assert!(parse_font("'r' { }\nalternate 'r' before () { }").is_err());
+}
+
+#[test]
+fn parses_kerning() {
+    let font = parse_font("kern 'r' 'y' (-10)\nkern 'T' 'o' (-25)").unwrap();
+    assert_eq!(font.kerning[&('r', 'y')], -10);
+    assert_eq!(font.kerning.len(), 2);
+
+    let err = parse_font("kern 'r' 'y' (-10)\nkern 'r' 'y' (5)").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 1));
+    assert!(parse_font("kern 'r' (-10)").is_err());
+}
+
+#[test]
+fn rejects_what_the_macro_rejects() {
+    let err = parse_font("metrics {\n  baseline (0)\n  x_height (-10)\n}").unwrap_err();
+    assert_eq!((err.line, err.column), (3, 3));
+    assert!(err
+        .message
+        .contains("x_height (-10) must be above baseline (0)"));
+    assert!(parse_font("metrics { line_gap (-1) }").is_err());
+
+    // y is checked against metrics that come later in the file
+    let source = "'a' {\n  line (0, -60) -> (0, 0)\n}\nmetrics { descender (-50) }";
+    let err = parse_font(source).unwrap_err();
+    assert_eq!((err.line, err.column), (2, 12));
+    assert!(err.message.contains("-50..=100"));
+
+    let err = parse_glyphs("'a' { line (0, 0) -> (120, 0) }").unwrap_err();
+    assert!(err.message.contains("outside the 0-100 glyph box"));
+    assert!(parse_font("ligature 'f' 'i' { line (0, 0) -> (200, 0) }").is_ok());
+    assert!(parse_glyphs("'a' { arc (50, 50) radius (0) from (0) -> (360) }").is_err());
+    assert!(parse_glyphs("'a' { restrict_width (150) }").is_err());
+    assert!(parse_font("kern 'a' 'b' (-150)").is_err());
+
+    // Metrics at the ends of i16 don't overflow the line height
+    let font = parse_font("metrics { descender (-32768) ascender (32767) line_gap (0) }").unwrap();
+    assert_eq!(font.metrics.line_height(), 65535);
+    let result = TextLayout::new(0.0, 0.0, 1.0, 1.0).layout(&font, "a\na");
+    assert_eq!(result.ascent, 32767.0 / 100.0 * 40.0);
+}
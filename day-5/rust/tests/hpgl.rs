@@ -0,0 +1,96 @@
+use day_5::drawing::{Drawing, DrawingOptions};
+use day_5::renderers::{ArcMode, HpglConfig, HpglDrawing};
+
+// No origin offset and 1 plotter unit per mm, so coordinates come out as given
+fn plain() -> HpglConfig {
+    HpglConfig {
+        units_per_mm: 1.0,
+        origin_x: 0.0,
+        origin_y: 0.0,
+        ..Default::default()
+    }
+}
+
+fn commands(hpgl: &mut HpglDrawing) -> Vec<String> {
+    hpgl.render().lines().map(String::from).collect()
+}
+
+#[test]
+fn writes_the_header_and_footer() {
+    let config = HpglConfig {
+        pen: 2,
+        velocity: Some(10.0),
+        ..plain()
+    };
+    let mut hpgl = HpglDrawing::new(DrawingOptions::default(), config);
+    assert_eq!(commands(&mut hpgl), ["IN;", "SP2;", "VS10;", "PU;", "SP0;"]);
+
+    // No VS without a velocity
+    let mut hpgl = HpglDrawing::new(DrawingOptions::default(), plain());
+    assert_eq!(commands(&mut hpgl)[..2], ["IN;", "SP1;"]);
+    assert!(!hpgl.render().contains("VS"));
+}
+
+#[test]
+fn lifts_the_pen_only_between_separate_strokes() {
+    let mut hpgl = HpglDrawing::new(DrawingOptions::default(), plain());
+    hpgl.line(0.0, 0.0, 10.0, 0.0);
+    hpgl.line(10.0, 0.0, 10.0, 10.0); // continues the first
+    hpgl.line(50.0, 50.0, 60.0, 50.0);
+    assert_eq!(
+        commands(&mut hpgl)[2..],
+        ["PU0,0;", "PD10,0;", "PD10,10;", "PU50,50;", "PD60,50;", "PU;", "SP0;"]
+    );
+
+    // Plotter units are mm times units_per_mm from the origin
+    let mut hpgl = HpglDrawing::new(DrawingOptions::default(), HpglConfig::default());
+    hpgl.line(0.0, 0.0, 1.0, 2.0);
+    assert_eq!(commands(&mut hpgl)[2..4], ["PU400,400;", "PD440,480;"]);
+}
+
+#[test]
+fn draws_arcs_around_their_centre() {
+    let mut hpgl = HpglDrawing::new(DrawingOptions::default(), plain());
+    // Anticlockwise, positive sweep
+    hpgl.arc(50.0, 50.0, 10.0, 0.0, 90.0);
+    // Clockwise, negative sweep
+    hpgl.arc(100.0, 50.0, 10.0, 180.0, 90.0);
+    assert_eq!(
+        commands(&mut hpgl)[2..8],
+        [
+            "PU60,50;",
+            "PD;",
+            "AA50,50,90,5;",
+            "PU90,50;",
+            "PD;",
+            "AA100,50,-90,5;",
+        ]
+    );
+
+    // An arc continuing a line keeps the pen down
+    let mut hpgl = HpglDrawing::new(DrawingOptions::default(), plain());
+    hpgl.line(0.0, 50.0, 60.0, 50.0);
+    hpgl.arc(50.0, 50.0, 10.0, 0.0, 360.0);
+    assert_eq!(
+        commands(&mut hpgl)[2..5],
+        ["PU0,50;", "PD60,50;", "AA50,50,360,5;"]
+    );
+}
+
+#[test]
+fn flattens_arcs_for_plotters_without_aa() {
+    let config = HpglConfig {
+        arc_mode: ArcMode::Flatten,
+        ..plain()
+    };
+    let mut hpgl = HpglDrawing::new(DrawingOptions::default(), config);
+    hpgl.arc(50.0, 50.0, 10.0, 0.0, 180.0);
+    let commands = commands(&mut hpgl);
+
+    let body = &commands[2..commands.len() - 2];
+    assert_eq!(body[0], "PU60,50;");
+    assert!(body.len() > 3);
+    assert!(body[1..].iter().all(|c| c.starts_with("PD") && c != "PD;"));
+    assert_eq!(body[body.len() - 1], "PD40,50;");
+    assert!(!commands.iter().any(|c| c.starts_with("AA")));
+}
@@ -0,0 +1,85 @@
+use day_5::glyph_file::parse_font;
+use day_5::kerning::{kern_pair, KerningConfig, KerningTable};
+use day_5::layout::{PlacedGlyph, TextLayout};
+use day_5::letters::define_font;
+use day_5::types::Pt;
+
+fn kern(table: &KerningTable, pair: &str) -> f64 {
+    let chars: Vec<char> = pair.chars().collect();
+    table.get(chars[0], chars[1])
+}
+
+fn ink(glyph: &PlacedGlyph) -> Vec<Pt<f64>> {
+    glyph.strokes.iter().flat_map(|s| s.flatten(0.1)).collect()
+}
+
+#[test]
+fn evens_out_the_gaps_between_pairs() {
+    let font = define_font();
+    let table = KerningTable::auto(&font, &KerningConfig::default());
+
+    // Two stems are already where they should be
+    assert!(kern(&table, "nn").abs() < 5.0);
+    // Narrow letters sit in the middle of their box and get pulled in
+    assert!(kern(&table, "ll") < -40.0);
+    assert!(kern(&table, "ta") < -20.0);
+    // Open sides get some of their space taken up
+    assert!(kern(&table, "ry") < kern(&table, "rn"));
+    assert!(kern(&table, "vy") < 0.0);
+    // Combining marks have nothing to space
+    assert_eq!(kern(&table, "a\u{301}"), 0.0);
+
+    let o = &font.glyphs[&'o'];
+    let config = KerningConfig::default();
+    assert_eq!(kern_pair(&font, o, o, &config), kern(&table, "oo"));
+}
+
+#[test]
+fn kerned_glyphs_never_touch() {
+    let font = define_font();
+    let table = KerningTable::auto(&font, &KerningConfig::default());
+    let layout = TextLayout::new(0.0, 0.0, 1.0, 1.0).kerning(table);
+    let result = layout.layout(&font, "AVATAR To yo LT wavy fly j7 k1 rx");
+
+    for pair in result.glyphs.windows(2) {
+        let (left, right) = (ink(&pair[0]), ink(&pair[1]));
+        for p in &left {
+            for q in right.iter().filter(|q| (q.1 - p.1).abs() < 1.0) {
+                assert!(q.0 > p.0, "{} and {} touch", pair[0].ch, pair[1].ch);
+            }
+        }
+    }
+}
+
+#[test]
+fn layout_applies_the_table() {
+    let font = define_font();
+    let mut table = KerningTable::new();
+    table.set('l', 'l', -50.0);
+
+    let plain = TextLayout::new(0.0, 0.0, 1.0, 1.0).layout(&font, "lll l");
+    let kerned = TextLayout::new(0.0, 0.0, 1.0, 1.0)
+        .kerning(table)
+        .layout(&font, "lll l");
+    let x = |result: &day_5::layout::LayoutResult, i: usize| result.glyphs[i].x;
+
+    // Half a 40 unit glyph closer for every pair, not across the space
+    assert_eq!(x(&kerned, 1), x(&plain, 1) - 20.0);
+    assert_eq!(x(&kerned, 2), x(&plain, 2) - 40.0);
+    assert_eq!(x(&kerned, 4), x(&plain, 4) - 40.0);
+    assert_eq!(kerned.width, plain.width - 40.0);
+}
+
+#[test]
+fn font_kerning_wins_over_measured() {
+    let mut font = define_font();
+    font.kerning = parse_font("kern 'r' 'y' (5)").unwrap().kerning;
+
+    let table = KerningTable::auto(&font, &KerningConfig::default());
+    assert_eq!(kern(&table, "ry"), 5.0);
+    assert!(kern(&table, "vy") < 0.0);
+
+    let manual = KerningTable::from_font(&font);
+    assert_eq!(manual.len(), 1);
+    assert_eq!(kern(&manual, "ry"), 5.0);
+}
@@ -0,0 +1,122 @@
+use day_5::layout::{Align, TextLayout};
+use day_5::letters::define_font;
+
+#[test]
+fn descenders_drop_below_the_baseline() {
+    let font = define_font();
+    let text = TextLayout::new(0.0, 100.0, 1.0, 1.0).layout(&font, "px");
+    let p = &text.glyphs[0];
+    let x = &text.glyphs[1];
+
+    let lowest = |strokes: &[day_5::types::Stroke<f64>]| {
+        strokes
+            .iter()
+            .map(|s| s.bounds().min_y)
+            .fold(f64::INFINITY, f64::min)
+    };
+    // p's stem runs down to the descender, -50 is half of the 40 unit glyph box
+    assert!((lowest(&p.strokes) - 80.0).abs() < 1e-9);
+    assert!((lowest(&x.strokes) - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn newlines_move_down_one_line_height() {
+    let font = define_font();
+    let layout = TextLayout::new(10.0, 500.0, 2.0, 1.0);
+    let text = layout.layout(&font, "ab\nc");
+
+    assert_eq!(text.lines.len(), 2);
+    assert!(text.is_complete());
+    let line_height = layout.line_height_for(&font.metrics);
+    // (140 + 50 + 30) / 100 * 40 * 2
+    assert!((line_height - 176.0).abs() < 1e-9);
+
+    let c = &text.glyphs[2];
+    assert_eq!((c.ch, c.index), ('c', 3));
+    assert_eq!(c.x, 10.0);
+    assert_eq!(c.y, 500.0 - line_height);
+    // 'b' ends at the right edge of its glyph box
+    assert_eq!(text.width, text.glyphs[0].advance + 80.0);
+}
+
+// Advances at scale 1 are 60, except 54 for the width restricted 'c'
+#[test]
+fn wraps_at_spaces() {
+    let font = define_font();
+    let text = TextLayout::new(0.0, 0.0, 1.0, 1.0)
+        .max_width(300.0)
+        .layout(&font, "ab cd ef gh");
+
+    let lines: Vec<String> = text
+        .lines
+        .iter()
+        .map(|line| {
+            text.glyphs[line.glyphs.clone()]
+                .iter()
+                .map(|g| g.ch)
+                .collect()
+        })
+        .collect();
+    assert_eq!(lines, ["ab cd", "ef gh"]);
+    assert_eq!(text.lines[1].baseline, -text.line_height);
+    assert_eq!(text.lines[0].width, 274.0);
+    // The space at the break is dropped but indices still point into the text
+    assert_eq!(text.glyphs[5].index, 6);
+}
+
+#[test]
+fn long_words_get_their_own_line() {
+    let font = define_font();
+    let text = TextLayout::new(0.0, 0.0, 1.0, 1.0)
+        .max_width(100.0)
+        .layout(&font, "a abcdef a");
+    let widths: Vec<f64> = text.lines.iter().map(|line| line.width).collect();
+    assert_eq!(widths, [40.0, 334.0, 40.0]);
+}
+
+#[test]
+fn aligns_lines_in_the_box() {
+    let font = define_font();
+    let layout = TextLayout::new(10.0, 0.0, 1.0, 1.0).max_width(300.0);
+
+    let right = layout.clone().align(Align::Right).layout(&font, "ab");
+    assert_eq!(right.lines[0].x, 10.0 + 300.0 - 100.0);
+    let center = layout.clone().align(Align::Center).layout(&font, "ab");
+    assert_eq!(center.glyphs[0].x, 10.0 + 100.0);
+
+    // Without max_width lines align to the widest one
+    let text = TextLayout::new(0.0, 0.0, 1.0, 1.0)
+        .align(Align::Right)
+        .layout(&font, "abc\na");
+    assert_eq!(text.lines[1].x, 120.0);
+}
+
+#[test]
+fn justify_stretches_spaces_except_on_the_last_line() {
+    let font = define_font();
+    let text = TextLayout::new(0.0, 0.0, 1.0, 1.0)
+        .max_width(400.0)
+        .align(Align::Justify)
+        .layout(&font, "ab c d ef");
+
+    // "ab c d" is 334 wide, the 66 left over is split between two spaces
+    assert_eq!(text.lines[0].width, 400.0);
+    let d = &text.glyphs[5];
+    assert_eq!(d.ch, 'd');
+    assert_eq!(d.x, 360.0);
+    assert_eq!(text.lines[1].width, 100.0);
+}
+
+#[test]
+fn hit_test_finds_the_glyph_under_a_point() {
+    let font = define_font();
+    let text = TextLayout::new(0.0, 0.0, 1.0, 1.0)
+        .line_height(100.0)
+        .layout(&font, "ab\ncd");
+
+    assert_eq!(text.hit_test(70.0, 10.0).map(|g| g.ch), Some('b'));
+    assert_eq!(text.hit_test(5.0, -90.0).map(|g| g.ch), Some('c'));
+    // Between the lines, below 'a's descender and above 'c's ascender
+    assert!(text.hit_test(5.0, -25.0).is_none());
+    assert_eq!(text.height(), 56.0 + 20.0 + 100.0);
+}
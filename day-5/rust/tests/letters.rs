@@ -0,0 +1,9 @@
+use day_5::letters::define_glyphs;
+
+#[test]
+fn every_printable_ascii_character_has_a_glyph() {
+    let glyphs = define_glyphs();
+
+    let missing: Vec<char> = (' '..='~').filter(|ch| !glyphs.contains_key(ch)).collect();
+    assert!(missing.is_empty(), "missing glyphs for {missing:?}");
+}
@@ -0,0 +1,76 @@
+use day_5::geometry::Bounds;
+use day_5::layout::{LayoutResult, TextLayout};
+use day_5::letters::define_font;
+use day_5::types::Stroke;
+
+fn layout(text: &str) -> LayoutResult {
+    TextLayout::new(0.0, 0.0, 1.0, 1.0).layout(&define_font(), text)
+}
+
+fn bounds(strokes: &[Stroke<f64>]) -> Bounds {
+    strokes
+        .iter()
+        .map(|s| s.bounds())
+        .reduce(Bounds::union)
+        .unwrap()
+}
+
+#[test]
+fn builds_accented_letters_from_base_and_mark() {
+    let composed = layout("é");
+    assert!(composed.is_complete());
+    assert_eq!(composed.glyphs.len(), 1);
+    assert_eq!(composed.glyphs[0].ch, 'é');
+
+    let e = layout("e");
+    let strokes = &composed.glyphs[0].strokes;
+    assert_eq!(strokes[..2], e.glyphs[0].strokes[..]);
+    // The accent sits above the x-height, 15 units of the 40 unit box up
+    let accent = bounds(&strokes[2..]);
+    assert!((accent.min_y - (40.0 + 0.15 * 40.0)).abs() < 1e-9);
+    assert_eq!(composed.glyphs[0].advance, e.glyphs[0].advance);
+
+    // Typed as e plus a combining acute it comes out the same
+    let decomposed = layout("e\u{301}");
+    assert_eq!(decomposed.glyphs.len(), 1);
+    assert_eq!(decomposed.glyphs[0].strokes, *strokes);
+}
+
+#[test]
+fn marks_stack_and_hang_below() {
+    // u with diaeresis and macron, the macron goes above the dots
+    let glyph = &layout("ǖ").glyphs[0];
+    let dots = bounds(&glyph.strokes[3..5]);
+    let macron = bounds(&glyph.strokes[5..]);
+    assert!(macron.min_y > dots.max_y);
+
+    // The cedilla starts at the bottom of the c
+    let glyph = &layout("ç").glyphs[0];
+    let cedilla = bounds(&glyph.strokes[1..]);
+    assert!((cedilla.max_y - 0.0).abs() < 1e-9);
+    assert!(cedilla.min_y < 0.0);
+}
+
+#[test]
+fn i_and_j_lose_their_dot() {
+    let glyph = &layout("í").glyphs[0];
+    // Stem and accent, no dot
+    assert_eq!(glyph.strokes.len(), 2);
+    assert!(glyph
+        .strokes
+        .iter()
+        .all(|s| !matches!(s, Stroke::Arc { .. })));
+
+    // A mark below keeps the dot, stem and dot plus the two cedilla strokes
+    let result = layout("i\u{327}");
+    assert_eq!(result.glyphs[0].strokes.len(), 4);
+}
+
+#[test]
+fn reports_marks_the_font_lacks() {
+    // a with ring below, U+0325 has no glyph
+    let result = layout("a\u{1e01}b");
+    assert_eq!(result.missing.len(), 1);
+    assert_eq!(result.missing[0].ch, '\u{1e01}');
+    assert_eq!(result.glyphs.len(), 2);
+}
@@ -0,0 +1,47 @@
+use day_5::optimize::{optimize, travel_distance, OptimizeOptions};
+use day_5::types::{Pt, Stroke};
+
+fn line(x1: f64, y1: f64, x2: f64, y2: f64) -> Stroke<f64> {
+    Stroke::Line {
+        from: Pt(x1, y1),
+        to: Pt(x2, y2),
+    }
+}
+
+#[test]
+fn merges_touching_strokes_into_one_path() {
+    // A zig-zag given out of order with the middle segment backwards
+    let strokes = vec![
+        line(20.0, 0.0, 30.0, 10.0),
+        line(0.0, 0.0, 10.0, 10.0),
+        line(20.0, 0.0, 10.0, 10.0),
+    ];
+    let result = optimize(&strokes, &OptimizeOptions::default());
+
+    assert_eq!(result.paths.len(), 1);
+    assert_eq!(result.paths[0].len(), 3);
+    assert!(result.travel_after.abs() < 1e-9);
+    assert!(result.travel_before > 0.0);
+}
+
+#[test]
+fn reorders_and_flips_paths_to_reduce_travel() {
+    // Three separate dashes, given far end first
+    let strokes = vec![
+        line(100.0, 0.0, 110.0, 0.0),
+        line(60.0, 0.0, 50.0, 0.0),
+        line(0.0, 0.0, 10.0, 0.0),
+    ];
+    let options = OptimizeOptions::default();
+    let result = optimize(&strokes, &options);
+
+    assert_eq!(result.paths.len(), 3);
+    assert!(result.travel_after < result.travel_before);
+    assert!((result.travel_after - 80.0).abs() < 1e-9);
+    assert!(
+        (travel_distance(result.strokes(), options.start, options.join_tolerance)
+            - result.travel_after)
+            .abs()
+            < 1e-9
+    );
+}
@@ -0,0 +1,113 @@
+use day_5::geometry::distance;
+use day_5::glyph_file::parse_font;
+use day_5::layout::TextLayout;
+use day_5::letters::define_font;
+use day_5::path_text::{PathLayout, TextPath};
+use day_5::types::{Pt, Stroke};
+
+fn circle(radius: f64) -> TextPath {
+    TextPath::from_strokes(
+        &[Stroke::Arc {
+            center: Pt(0.0, 0.0),
+            radius,
+            start: 0.0,
+            end: 360.0,
+        }],
+        0.01,
+    )
+}
+
+#[test]
+fn measures_and_wraps_closed_paths() {
+    let square = TextPath::from_points(
+        &[Pt(0.0, 0.0), Pt(10.0, 0.0), Pt(10.0, 10.0), Pt(0.0, 10.0)],
+        true,
+    );
+    assert_eq!(square.length(), 40.0);
+    assert_eq!(square.point_at(15.0), Pt(10.0, 5.0));
+    assert_eq!(square.point_at(45.0), Pt(5.0, 0.0));
+    assert_eq!(square.tangent_at(25.0), Pt(-1.0, 0.0));
+
+    let open = TextPath::from_points(&[Pt(0.0, 0.0), Pt(10.0, 0.0)], false);
+    assert_eq!(open.point_at(15.0), Pt(15.0, 0.0));
+
+    assert!(circle(100.0).is_closed());
+    assert!((circle(100.0).length() - 200.0 * std::f64::consts::PI).abs() < 0.1);
+}
+
+#[test]
+fn straight_path_matches_normal_layout() {
+    let font = define_font();
+    let layout = TextLayout::new(10.0, 0.0, 1.0, 1.0);
+    let path = TextPath::from_points(&[Pt(0.0, 0.0), Pt(1000.0, 0.0)], false);
+
+    let flat = layout.layout(&font, "ab");
+    let on_path = PathLayout::new(layout.clone()).layout(&font, "ab", &path);
+    for (a, b) in flat.glyphs.iter().zip(&on_path.glyphs) {
+        assert_eq!((a.x, a.y, b.angle), (b.x, b.y, 0.0));
+        assert_eq!(a.strokes, b.strokes);
+    }
+
+    // Missing glyphs are still reported
+    let on_path = PathLayout::new(layout).layout(&font, "a\u{2603}", &path);
+    assert!(!on_path.is_complete());
+    assert_eq!(on_path.missing[0].ch, '\u{2603}');
+    assert_eq!(on_path.strokes().count(), flat.glyphs[0].strokes.len());
+}
+
+#[test]
+fn rigid_glyphs_turn_with_the_circle() {
+    let font = define_font();
+    let text =
+        PathLayout::new(TextLayout::new(0.0, 0.0, 1.0, 1.0)).layout(&font, "x", &circle(100.0));
+    let x = &text.glyphs[0];
+
+    // The glyph box is 40 wide, its bottom center sits on the circle 20 along
+    // and the bottom left corner is on the tangent there
+    let angle = 20.0f64 / 100.0;
+    assert!((x.angle - (90.0 + angle.to_degrees())).abs() < 0.1);
+    let corner = 100.0f64.hypot(20.0);
+    assert!((distance(Pt(x.x, x.y), Pt(0.0, 0.0)) - corner).abs() < 0.1);
+}
+
+#[test]
+fn rigid_ligatures_turn_around_their_own_center() {
+    let font = parse_font(
+        "
+        ligature 'f' 'i' {
+            line (0, 0) -> (200, 0)
+        }
+        ",
+    )
+    .unwrap();
+    let text =
+        PathLayout::new(TextLayout::new(0.0, 0.0, 1.0, 1.0)).layout(&font, "fi", &circle(100.0));
+    let fi = &text.glyphs[0];
+
+    // Two 40 unit glyph boxes, so the center is 40 along the circle
+    assert_eq!(fi.width, 80.0);
+    let angle = 40.0f64 / 100.0;
+    assert!((fi.angle - (90.0 + angle.to_degrees())).abs() < 0.1);
+    let corner = 100.0f64.hypot(40.0);
+    assert!((distance(Pt(fi.x, fi.y), Pt(0.0, 0.0)) - corner).abs() < 0.1);
+}
+
+#[test]
+fn bent_glyphs_follow_the_curve() {
+    let font = define_font();
+    let text = PathLayout::new(TextLayout::new(0.0, 0.0, 1.0, 1.0))
+        .bend(true)
+        .layout(&font, "x", &circle(100.0));
+
+    // Counter-clockwise the left side is the inside, 'x' is 40 tall
+    for stroke in &text.glyphs[0].strokes {
+        let Stroke::Polyline { points } = stroke else {
+            panic!("bent strokes are polylines");
+        };
+        assert!(points.len() > 10);
+        for &p in points {
+            let r = distance(p, Pt(0.0, 0.0));
+            assert!((59.9..=100.1).contains(&r), "{r}");
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use day_5::drawing::{Drawing, DrawingOptions};
+use day_5::renderers::{LineCap, PngConfig, PngDrawing};
+
+fn options() -> DrawingOptions {
+    DrawingOptions {
+        line_width: 4.0,
+        color: [1.0, 0.0, 0.0],
+        ..Default::default()
+    }
+}
+
+fn small_page() -> PngConfig {
+    PngConfig {
+        width: 100.0,
+        height: 50.0,
+        ..Default::default()
+    }
+}
+
+// r, g, b, a of the pixel at page point x, y
+fn pixel(png: &PngDrawing, x: f64, y: f64) -> [u8; 4] {
+    let scale = png.config().dpi / 72.0;
+    let height = png.pixmap().height() as f64;
+    let c = png
+        .pixmap()
+        .pixel((x * scale) as u32, (height - y * scale) as u32)
+        .unwrap();
+    [c.red(), c.green(), c.blue(), c.alpha()]
+}
+
+#[test]
+fn strokes_over_the_background_with_y_up() {
+    let mut png = PngDrawing::new(options(), small_page()).unwrap();
+    png.line(10.0, 10.0, 90.0, 10.0);
+    png.arc(50.0, 25.0, 10.0, 90.0, 270.0);
+
+    assert_eq!(pixel(&png, 50.0, 10.0), [255, 0, 0, 255]);
+    assert_eq!(pixel(&png, 40.0, 25.0), [255, 0, 0, 255]);
+    // The arc only covers the left half
+    assert_eq!(pixel(&png, 60.0, 25.0), [255, 255, 255, 255]);
+    assert_eq!(pixel(&png, 50.0, 40.0), [255, 255, 255, 255]);
+}
+
+#[test]
+fn scales_with_dpi_and_keeps_caps() {
+    let config = PngConfig {
+        dpi: 144.0,
+        background: None,
+        line_cap: LineCap::Butt,
+        ..small_page()
+    };
+    let mut png = PngDrawing::new(options(), config).unwrap();
+    assert_eq!((png.pixmap().width(), png.pixmap().height()), (200, 100));
+    png.line(20.0, 20.0, 80.0, 20.0);
+
+    assert_eq!(pixel(&png, 10.0, 10.0), [0, 0, 0, 0]);
+    assert_eq!(pixel(&png, 21.0, 21.0)[3], 255);
+    // Butt caps stop at the end points
+    assert_eq!(pixel(&png, 18.5, 20.0)[3], 0);
+
+    let round = PngConfig {
+        background: None,
+        ..small_page()
+    };
+    let mut png = PngDrawing::new(options(), round).unwrap();
+    png.line(20.0, 20.0, 80.0, 20.0);
+    assert_eq!(pixel(&png, 19.0, 19.5)[3], 255);
+}
+
+#[test]
+fn encodes_png_and_data_uri() {
+    let mut png = PngDrawing::new(options(), small_page()).unwrap();
+    png.circle(50.0, 25.0, 10.0);
+
+    let bytes = png.encode_png().unwrap();
+    assert_eq!(&bytes[..8], b"\x89PNG\r\n\x1a\n");
+
+    let uri = png.render();
+    let data = uri.strip_prefix("data:image/png;base64,").unwrap();
+    assert_eq!(data.len(), bytes.len().div_ceil(3) * 4);
+    assert!(data.starts_with("iVBORw0KGgo"));
+
+    let empty = PngConfig {
+        width: 0.0,
+        ..small_page()
+    };
+    assert!(PngDrawing::new(options(), empty).is_err());
+}
@@ -0,0 +1,43 @@
+use day_5::drawing::{Drawing, DrawingOptions};
+use day_5::renderers::PostScriptDrawing;
+
+// The arc commands, operands and operator
+fn arcs(ps: &mut PostScriptDrawing) -> Vec<String> {
+    ps.render()
+        .lines()
+        .filter(|line| line.ends_with(" arc") || line.ends_with(" arcn"))
+        .map(String::from)
+        .collect()
+}
+
+#[test]
+fn picks_arc_or_arcn_by_direction() {
+    let mut ps = PostScriptDrawing::new(DrawingOptions::default());
+    ps.arc(50.0, 50.0, 10.0, 0.0, 90.0);
+    ps.arc(50.0, 50.0, 10.0, 90.0, 0.0);
+    // Past a whole turn and below zero, the angles are passed on as they are
+    ps.arc(50.0, 50.0, 10.0, 240.0, 390.0);
+    ps.arc(50.0, 50.0, 10.0, 0.0, -180.0);
+    assert_eq!(
+        arcs(&mut ps),
+        [
+            "50 50 10 0 90 arc",
+            "50 50 10 90 0 arcn",
+            "50 50 10 240 390 arc",
+            "50 50 10 0 -180 arcn",
+        ]
+    );
+}
+
+#[test]
+fn strokes_every_arc_on_its_own() {
+    let mut ps = PostScriptDrawing::new(DrawingOptions::default());
+    ps.arc(50.0, 50.0, 10.0, 0.0, 360.0);
+    let out = ps.render();
+    let lines: Vec<_> = out.lines().collect();
+    let i = lines.iter().position(|l| l.ends_with(" arc")).unwrap();
+    assert_eq!(lines[i - 1], "newpath");
+    assert_eq!(lines[i + 1], "stroke");
+    assert!(out.starts_with("%!PS-Adobe-3.0"));
+    assert!(out.ends_with("%%EOF"));
+}
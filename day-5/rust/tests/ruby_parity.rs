@@ -0,0 +1,531 @@
+// Parity between the Ruby implementation in ../ruby and this crate.
+//
+// Glyphs are compared as ink: every stroke is sampled into points and two
+// drawings match when no point of one is further than a tolerance from the
+// other (Hausdorff distance), so a circle drawn as two half arcs still equals
+// a full circle. The Ruby glyph definitions are read from letters.rb and the
+// reference outputs are the logo-ruby.* files generate_postscript.rb wrote.
+use std::collections::BTreeMap;
+
+use day_5::drawing::{Drawing, DrawingOptions};
+use day_5::geometry::{distance, lerp};
+use day_5::layout::{place_strokes, TextLayout};
+use day_5::letters::define_font;
+use day_5::renderers::{GcodeConfig, GcodeDrawing, PostScriptDrawing, SvgDrawing};
+use day_5::types::{Pt, Stroke};
+
+const RUBY_LETTERS: &str = include_str!("../../ruby/lib/glyphs/letters.rb");
+const RUBY_SVG: &str = include_str!("../../ruby/logo-ruby.svg");
+const RUBY_PS: &str = include_str!("../../ruby/logo-ruby.ps");
+const RUBY_GCODE: &str = include_str!("../../ruby/logo-ruby.gcode");
+
+// Glyphs that were redrawn on purpose and no longer match letters.rb
+const KNOWN_DIFFERENCES: &[(char, &str)] = &[
+    ('2', "drawn with cubics"),
+    ('b', "stem to the ascender"),
+    ('d', "stem to the ascender"),
+    ('h', "stem to the ascender"),
+    ('j', "hook on the descender"),
+    ('k', "stem to the ascender"),
+    ('l', "stem to the ascender"),
+    ('p', "stem to the descender"),
+    ('q', "tail to the descender"),
+    ('s', "drawn with cubics"),
+    ('y', "stem to the descender"),
+];
+
+fn known_difference(ch: char) -> bool {
+    KNOWN_DIFFERENCES.iter().any(|(known, _)| *known == ch)
+}
+
+struct RubyGlyph {
+    strokes: Vec<Stroke<f64>>,
+    width_restriction: u8,
+}
+
+// Reads the GlyphDSL calls in letters.rb, enough of Ruby for that file
+fn ruby_glyphs() -> BTreeMap<char, RubyGlyph> {
+    let mut glyphs = BTreeMap::new();
+    let mut current: Option<(char, RubyGlyph)> = None;
+
+    for line in RUBY_LETTERS.lines() {
+        let line = line.split('#').next().unwrap().trim();
+        if let Some(rest) = line.strip_prefix("GlyphDSL.define_glyph ") {
+            let name = rest.trim_end_matches(" do");
+            let name = name
+                .strip_prefix(':')
+                .or_else(|| name.strip_prefix('\'')?.strip_suffix('\''))
+                .unwrap_or_else(|| panic!("unexpected glyph name {name:?}"));
+            let mut chars = name.chars();
+            let ch = chars.next().unwrap();
+            assert!(chars.next().is_none(), "glyph name {name:?}");
+            let glyph = RubyGlyph {
+                strokes: Vec::new(),
+                width_restriction: 100,
+            };
+            current = Some((ch, glyph));
+            continue;
+        }
+        if line == "end" {
+            if let Some((ch, glyph)) = current.take() {
+                glyphs.insert(ch, glyph);
+            }
+            continue;
+        }
+        let Some((_, glyph)) = current.as_mut() else {
+            continue;
+        };
+        let Some((command, args)) = line.split_once(' ') else {
+            continue;
+        };
+        let n: Vec<f64> = args
+            .split(',')
+            .map(|arg| arg.trim().parse().unwrap())
+            .collect();
+        let stroke = match command {
+            "line" => line_stroke(n[0], n[1], n[2], n[3]),
+            "vertical_line" => line_stroke(n[0], n[1], n[0], n[2]),
+            "horizontal_line" => line_stroke(n[0], n[2], n[1], n[2]),
+            "circle" => arc_stroke(n[0], n[1], n[2], 0.0, 360.0),
+            "arc" => arc_stroke(n[0], n[1], n[2], n[3], n[4]),
+            "restrict_width" => {
+                glyph.width_restriction = n[0].clamp(0.0, 100.0) as u8;
+                continue;
+            }
+            other => panic!("unknown GlyphDSL command {other:?}"),
+        };
+        glyph.strokes.push(stroke);
+    }
+
+    glyphs
+}
+
+fn line_stroke(x1: f64, y1: f64, x2: f64, y2: f64) -> Stroke<f64> {
+    Stroke::Line {
+        from: Pt(x1, y1),
+        to: Pt(x2, y2),
+    }
+}
+
+// The Ruby SVG and G-code renderers go from start to end the short way
+// round the sign, counter-clockwise when end > start, like Stroke::Arc
+fn arc_stroke(x: f64, y: f64, radius: f64, start: f64, end: f64) -> Stroke<f64> {
+    Stroke::Arc {
+        center: Pt(x, y),
+        radius,
+        start,
+        end,
+    }
+}
+
+// Points along the strokes no more than `step` apart
+fn ink(strokes: &[Stroke<f64>], step: f64) -> Vec<Pt<f64>> {
+    let mut points = Vec::new();
+    for stroke in strokes {
+        let flat = stroke.flatten(step / 10.0);
+        points.push(flat[0]);
+        for pair in flat.windows(2) {
+            let pieces = (distance(pair[0], pair[1]) / step).ceil().max(1.0) as usize;
+            for k in 1..=pieces {
+                points.push(lerp(pair[0], pair[1], k as f64 / pieces as f64));
+            }
+        }
+    }
+    points
+}
+
+// Furthest any point of one set is from the other set
+fn hausdorff(a: &[Pt<f64>], b: &[Pt<f64>]) -> f64 {
+    let directed = |from: &[Pt<f64>], to: &[Pt<f64>]| {
+        from.iter()
+            .map(|&p| {
+                to.iter()
+                    .map(|&q| distance(p, q))
+                    .fold(f64::INFINITY, f64::min)
+            })
+            .fold(0.0, f64::max)
+    };
+    match (a.is_empty(), b.is_empty()) {
+        (true, true) => 0.0,
+        (true, false) | (false, true) => f64::INFINITY,
+        _ => directed(a, b).max(directed(b, a)),
+    }
+}
+
+// Glyph box units, sampling alone stays well under this
+const GLYPH_TOLERANCE: f64 = 0.5;
+
+#[test]
+fn glyph_definitions_match_ruby() {
+    let font = define_font();
+    let mut differences = Vec::new();
+
+    for (&ch, ruby) in &ruby_glyphs() {
+        let Some(glyph) = font.glyphs.get(&ch) else {
+            differences.push((ch, "missing in letters.rs".to_string()));
+            continue;
+        };
+        let rust = place_strokes(&glyph.strokes, 0.0, 0.0, 100.0, 100.0);
+        let gap = hausdorff(&ink(&ruby.strokes, 0.5), &ink(&rust, 0.5));
+        let width = glyph.width_restriction.unwrap_or(100);
+        if gap > GLYPH_TOLERANCE {
+            differences.push((ch, format!("strokes differ by up to {gap:.1}")));
+        } else if width != ruby.width_restriction {
+            differences.push((
+                ch,
+                format!("width {width} vs {} in Ruby", ruby.width_restriction),
+            ));
+        }
+    }
+
+    let unexpected: Vec<_> = differences
+        .iter()
+        .filter(|(ch, _)| !known_difference(*ch))
+        .collect();
+    let stale: Vec<_> = KNOWN_DIFFERENCES
+        .iter()
+        .filter(|(known, _)| !differences.iter().any(|(ch, _)| ch == known))
+        .collect();
+    assert!(
+        unexpected.is_empty() && stale.is_empty(),
+        "glyphs that disagree with letters.rb: {unexpected:#?}\nlisted as different but now match: {stale:#?}"
+    );
+}
+
+// A rendered file split into the glyphs it draws, in order
+type Letters = Vec<(char, Vec<Stroke<f64>>)>;
+
+fn numbers(s: &str) -> Vec<f64> {
+    s.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty())
+        .map(|t| t.parse().unwrap_or_else(|_| panic!("not a number: {t:?}")))
+        .collect()
+}
+
+fn attr(tag: &str, name: &str) -> f64 {
+    attr_str(tag, name)
+        .parse()
+        .unwrap_or_else(|_| panic!("bad {name} in {tag}"))
+}
+
+fn attr_str<'a>(tag: &'a str, name: &str) -> &'a str {
+    let start = tag
+        .find(&format!(" {name}=\""))
+        .unwrap_or_else(|| panic!("no {name} in {tag}"))
+        + name.len()
+        + 3;
+    let len = tag[start..].find('"').unwrap();
+    &tag[start..start + len]
+}
+
+// SVG endpoint arc to a center arc, angles in SVG's y down space
+fn svg_arc(from: Pt<f64>, r: f64, large: bool, sweep: bool, to: Pt<f64>) -> Stroke<f64> {
+    let mid = lerp(from, to, 0.5);
+    let half = distance(from, to) / 2.0;
+    let h = (r * r - half * half).max(0.0).sqrt();
+    let (dx, dy) = (
+        (to.0 - from.0) / (2.0 * half),
+        (to.1 - from.1) / (2.0 * half),
+    );
+    // Perpendicular to the chord, side picked by the flags
+    let side = if large != sweep { 1.0 } else { -1.0 };
+    let center = Pt(mid.0 - dy * h * side, mid.1 + dx * h * side);
+    let angle = |p: Pt<f64>| (p.1 - center.1).atan2(p.0 - center.0).to_degrees();
+    let start = angle(from);
+    let mut sweep_deg = angle(to) - start;
+    if sweep && sweep_deg < 0.0 {
+        sweep_deg += 360.0;
+    } else if !sweep && sweep_deg > 0.0 {
+        sweep_deg -= 360.0;
+    }
+    arc_stroke(center.0, center.1, r, start, start + sweep_deg)
+}
+
+fn parse_svg(svg: &str) -> Letters {
+    let mut letters: Letters = Vec::new();
+    for tag in svg.split('<').map(|t| t.trim()) {
+        if tag.starts_with("g ") {
+            let id = attr_str(tag, "id");
+            letters.push((id.chars().last().unwrap(), Vec::new()));
+            continue;
+        }
+        let Some((_, strokes)) = letters.last_mut() else {
+            continue;
+        };
+        if tag.starts_with("line ") {
+            strokes.push(line_stroke(
+                attr(tag, "x1"),
+                attr(tag, "y1"),
+                attr(tag, "x2"),
+                attr(tag, "y2"),
+            ));
+        } else if tag.starts_with("circle ") {
+            strokes.push(arc_stroke(
+                attr(tag, "cx"),
+                attr(tag, "cy"),
+                attr(tag, "r"),
+                0.0,
+                360.0,
+            ));
+        } else if tag.starts_with("polyline ") {
+            let n = numbers(attr_str(tag, "points"));
+            let points = n.chunks(2).map(|p| Pt(p[0], p[1])).collect();
+            strokes.push(Stroke::Polyline { points });
+        } else if tag.starts_with("path ") {
+            strokes.extend(svg_path(attr_str(tag, "d")));
+        }
+    }
+    letters
+}
+
+fn svg_path(d: &str) -> Vec<Stroke<f64>> {
+    let mut strokes = Vec::new();
+    let mut current = Pt(0.0, 0.0);
+    // Split before every command letter
+    let mut commands = Vec::new();
+    for (i, c) in d.char_indices() {
+        if c.is_ascii_alphabetic() {
+            commands.push(i);
+        }
+    }
+    commands.push(d.len());
+    for pair in commands.windows(2) {
+        let command = &d[pair[0]..pair[1]];
+        let n = numbers(&command[1..]);
+        let pt = |i: usize| Pt(n[i], n[i + 1]);
+        match &command[..1] {
+            "M" => current = pt(0),
+            "L" => {
+                strokes.push(Stroke::Line {
+                    from: current,
+                    to: pt(0),
+                });
+                current = pt(0);
+            }
+            "A" => {
+                let to = pt(5);
+                strokes.push(svg_arc(current, n[0], n[3] != 0.0, n[4] != 0.0, to));
+                current = to;
+            }
+            "Q" => {
+                strokes.push(Stroke::Quadratic {
+                    from: current,
+                    ctrl: pt(0),
+                    to: pt(2),
+                });
+                current = pt(2);
+            }
+            "C" => {
+                strokes.push(Stroke::Cubic {
+                    from: current,
+                    ctrl1: pt(0),
+                    ctrl2: pt(2),
+                    to: pt(4),
+                });
+                current = pt(4);
+            }
+            other => panic!("unsupported path command {other:?}"),
+        }
+    }
+    strokes
+}
+
+fn parse_postscript(ps: &str) -> Letters {
+    let mut letters: Letters = Vec::new();
+    let mut stack: Vec<f64> = Vec::new();
+    let mut current = Pt(0.0, 0.0);
+
+    for line in ps.lines() {
+        if let Some(ch) = line.strip_prefix("% Letter: ") {
+            letters.push((ch.chars().next().unwrap(), Vec::new()));
+            continue;
+        }
+        if line.starts_with('%') {
+            continue;
+        }
+        for token in line.split_whitespace() {
+            if let Ok(n) = token.parse() {
+                stack.push(n);
+                continue;
+            }
+            let mut pop = |count: usize| stack.split_off(stack.len() - count);
+            let Some((_, strokes)) = letters.last_mut() else {
+                stack.clear();
+                continue;
+            };
+            match token {
+                "moveto" => {
+                    let n = pop(2);
+                    current = Pt(n[0], n[1]);
+                }
+                "lineto" => {
+                    let n = pop(2);
+                    strokes.push(Stroke::Line {
+                        from: current,
+                        to: Pt(n[0], n[1]),
+                    });
+                    current = Pt(n[0], n[1]);
+                }
+                "curveto" => {
+                    let n = pop(6);
+                    strokes.push(Stroke::Cubic {
+                        from: current,
+                        ctrl1: Pt(n[0], n[1]),
+                        ctrl2: Pt(n[2], n[3]),
+                        to: Pt(n[4], n[5]),
+                    });
+                    current = Pt(n[4], n[5]);
+                }
+                // arc always turns counter-clockwise and arcn clockwise
+                "arc" | "arcn" => {
+                    let n = pop(5);
+                    let mut end = n[4];
+                    if token == "arc" {
+                        while end < n[3] {
+                            end += 360.0;
+                        }
+                    } else {
+                        while end > n[3] {
+                            end -= 360.0;
+                        }
+                    }
+                    let arc = arc_stroke(n[0], n[1], n[2], n[3], end);
+                    current = arc.end_point();
+                    strokes.push(arc);
+                }
+                _ => {}
+            }
+        }
+    }
+    letters
+}
+
+fn parse_gcode(gcode: &str) -> Letters {
+    let mut letters: Letters = Vec::new();
+    let mut current = Pt(0.0, 0.0);
+    let mut pen_down = false;
+
+    for line in gcode.lines() {
+        if let Some(ch) = line.strip_prefix("; Letter: ") {
+            letters.push((ch.chars().next().unwrap(), Vec::new()));
+            continue;
+        }
+        let (code, comment) = line.split_once(';').unwrap_or((line, ""));
+        if code.starts_with("M280") {
+            pen_down = comment.contains("Pen down");
+            continue;
+        }
+        let mut words = code.split_whitespace();
+        let Some(command) = words.next() else {
+            continue;
+        };
+        let value = |axis: char, default: f64| {
+            code.split_whitespace()
+                .find_map(|w| w.strip_prefix(axis)?.parse().ok())
+                .unwrap_or(default)
+        };
+        let to = Pt(value('X', current.0), value('Y', current.1));
+        let strokes = letters.last_mut().map(|(_, strokes)| strokes);
+        match (command, strokes) {
+            ("G1", Some(strokes)) if pen_down => {
+                strokes.push(Stroke::Line { from: current, to });
+            }
+            ("G2" | "G3", Some(strokes)) if pen_down => {
+                let center = Pt(current.0 + value('I', 0.0), current.1 + value('J', 0.0));
+                let angle = |p: Pt<f64>| (p.1 - center.1).atan2(p.0 - center.0).to_degrees();
+                let start = angle(current);
+                let mut sweep = angle(to) - start;
+                if command == "G3" && sweep <= 0.0 {
+                    sweep += 360.0;
+                } else if command == "G2" && sweep >= 0.0 {
+                    sweep -= 360.0;
+                }
+                let radius = distance(center, current);
+                strokes.push(arc_stroke(center.0, center.1, radius, start, start + sweep));
+            }
+            _ => {}
+        }
+        current = to;
+    }
+    letters
+}
+
+// Same glyphs in the same order, and the ones defined alike are drawn alike
+fn assert_same_letters(name: &str, ruby: &Letters, rust: &Letters, tolerance: f64) {
+    let order = |letters: &Letters| letters.iter().map(|(ch, _)| *ch).collect::<String>();
+    assert_eq!(order(rust), order(ruby), "{name}: glyph order");
+
+    for (index, ((ch, ruby), (_, rust))) in ruby.iter().zip(rust).enumerate() {
+        if known_difference(*ch) {
+            continue;
+        }
+        let gap = hausdorff(&ink(ruby, tolerance / 4.0), &ink(rust, tolerance / 4.0));
+        assert!(
+            gap <= tolerance,
+            "{name}: {ch:?} at {index} is {gap:.3} away from the Ruby output"
+        );
+    }
+}
+
+fn genurary() -> day_5::layout::LayoutResult {
+    TextLayout::new(100.0, 400.0, 1.0, 1.0).layout(&define_font(), "genurary")
+}
+
+#[test]
+fn svg_matches_logo_ruby_svg() {
+    let mut svg = SvgDrawing::new(DrawingOptions::default());
+    svg.draw_text(&genurary());
+    assert_same_letters("svg", &parse_svg(RUBY_SVG), &parse_svg(&svg.render()), 0.5);
+}
+
+#[test]
+fn postscript_matches_logo_ruby_ps() {
+    let mut ps = PostScriptDrawing::new(DrawingOptions::default());
+    ps.draw_text(&genurary());
+    assert_same_letters(
+        "postscript",
+        &parse_postscript(RUBY_PS),
+        &parse_postscript(&ps.render()),
+        0.5,
+    );
+}
+
+#[test]
+fn gcode_matches_logo_ruby_gcode() {
+    // The settings generate_postscript.rb uses
+    let text = TextLayout::new(10.0, 50.0, 0.5, 1.5).layout(&define_font(), "genurary");
+    let mut gcode = GcodeDrawing::new(
+        DrawingOptions {
+            line_width: 0.5,
+            ..Default::default()
+        },
+        GcodeConfig {
+            feed_rate: 1500.0,
+            pen_up_position: 80,
+            pen_down_position: 40,
+            origin_x: 20.0,
+            origin_y: 20.0,
+            ..Default::default()
+        },
+    );
+    gcode.draw_text(&text);
+    assert_same_letters(
+        "gcode",
+        &parse_gcode(RUBY_GCODE),
+        &parse_gcode(&gcode.render()),
+        0.1,
+    );
+}
+
+#[test]
+fn parsers_see_every_stroke() {
+    // A broken parser returning nothing would make every comparison pass
+    let svg = parse_svg(RUBY_SVG);
+    assert_eq!(svg.len(), 8);
+    assert_eq!(svg[1].1.len(), 2);
+    let ps = parse_postscript(RUBY_PS);
+    assert_eq!(ps[2].1.len(), 3);
+    let gcode = parse_gcode(RUBY_GCODE);
+    assert_eq!(gcode[0].0, 'g');
+    assert_eq!(gcode[0].1.len(), 3);
+}
@@ -0,0 +1,108 @@
+use day_5::geometry::{distance, segment_distance};
+use day_5::layout::TextLayout;
+use day_5::letters::define_font;
+use day_5::sketch::{sketch, sketch_layout, SketchConfig};
+use day_5::types::{Pt, Stroke};
+
+fn polyline(stroke: &Stroke<f64>) -> &[Pt<f64>] {
+    match stroke {
+        Stroke::Polyline { points } => points,
+        _ => panic!("sketched strokes are polylines"),
+    }
+}
+
+fn line() -> Vec<Stroke<f64>> {
+    vec![Stroke::Line {
+        from: Pt(0.0, 0.0),
+        to: Pt(100.0, 0.0),
+    }]
+}
+
+#[test]
+fn same_seed_same_drawing() {
+    let config = SketchConfig::default();
+    assert_eq!(sketch(&line(), &config, 7), sketch(&line(), &config, 7));
+    assert_ne!(sketch(&line(), &config, 7), sketch(&line(), &config, 8));
+
+    // The same for a whole layout, glyph by glyph
+    let font = define_font();
+    let layout = TextLayout::new(0.0, 0.0, 1.0, 1.0);
+    let mut a = layout.layout(&font, "genurary");
+    let mut b = a.clone();
+    sketch_layout(&mut a, &config, 7);
+    sketch_layout(&mut b, &config, 7);
+    assert_eq!(
+        a.strokes().collect::<Vec<_>>(),
+        b.strokes().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn stays_close_to_the_stroke() {
+    let config = SketchConfig::default();
+    let sketched = sketch(&line(), &config, 3);
+    assert_eq!(sketched.len(), 1);
+    let points = polyline(&sketched[0]);
+
+    // Moved ends, run on past them and wobbles add up to at most this much
+    let reach = config.jitter * 2f64.sqrt() + config.overshoot + config.wobble;
+    for &p in points {
+        assert!(segment_distance(p, Pt(0.0, 0.0), Pt(100.0, 0.0)) <= reach);
+    }
+    // Long enough that it isn't a straight line any more
+    assert!(points.len() > 10);
+    assert!(points.iter().any(|p| p.1.abs() > 0.1));
+
+    // Without roughness it is the stroke
+    let smooth = SketchConfig {
+        roughness: 0.0,
+        ..Default::default()
+    };
+    let sketched = sketch(&line(), &smooth, 3);
+    let points = polyline(&sketched[0]);
+    assert_eq!(points[0], Pt(0.0, 0.0));
+    assert_eq!(points[points.len() - 1], Pt(100.0, 0.0));
+    assert!(points.iter().all(|p| p.1 == 0.0));
+}
+
+#[test]
+fn double_strokes_differ() {
+    let config = SketchConfig {
+        double: true,
+        ..Default::default()
+    };
+    let sketched = sketch(&line(), &config, 3);
+    assert_eq!(sketched.len(), 2);
+    assert_ne!(sketched[0], sketched[1]);
+
+    // Short strokes like dots barely move
+    let dot = [Stroke::Arc {
+        center: Pt(50.0, 50.0),
+        radius: 1.0,
+        start: 0.0,
+        end: 360.0,
+    }];
+    for stroke in sketch(&dot, &config, 3) {
+        for &p in polyline(&stroke) {
+            assert!((distance(p, Pt(50.0, 50.0)) - 1.0).abs() < 1.0);
+        }
+    }
+}
+
+#[test]
+fn scaled_config_sketches_the_same_shape() {
+    // The same line in mm, with the config scaled to match, is the same drawing
+    let mm = 25.4 / 72.0;
+    let config = SketchConfig::default();
+    let small = vec![Stroke::Line {
+        from: Pt(0.0, 0.0),
+        to: Pt(100.0 * mm, 0.0),
+    }];
+    let points = sketch(&line(), &config, 5);
+    let scaled = sketch(&small, &config.scaled(mm), 5);
+    let (points, scaled) = (polyline(&points[0]), polyline(&scaled[0]));
+    assert_eq!(points.len(), scaled.len());
+    for (p, q) in points.iter().zip(scaled) {
+        assert!(distance(Pt(p.0 * mm, p.1 * mm), *q) < 1e-9);
+    }
+}
@@ -0,0 +1,61 @@
+use day_5::layout::{LayoutResult, TextLayout};
+use day_5::letters::define_font;
+use day_5::substitution;
+
+fn layout(text: &str, seed: u64) -> LayoutResult {
+    TextLayout::new(0.0, 0.0, 1.0, 1.0)
+        .seed(seed)
+        .layout(&define_font(), text)
+}
+
+#[test]
+fn ligatures_replace_their_chars() {
+    let font = define_font();
+    let result = layout("fix", 0);
+    assert!(result.is_complete());
+    assert_eq!(result.glyphs.len(), 2);
+    assert_eq!((result.glyphs[0].ch, result.glyphs[0].index), ('f', 0));
+    assert_eq!(result.glyphs[0].strokes.len(), 4);
+    assert_eq!((result.glyphs[1].ch, result.glyphs[1].index), ('x', 2));
+
+    // Takes the room of both boxes, with the spacing after the i
+    let i = &font.glyphs[&'i'];
+    let layout = TextLayout::new(0.0, 0.0, 1.0, 1.0);
+    assert_eq!(result.glyphs[0].advance, 40.0 + layout.advance(i));
+
+    // The longest ligature wins, "ffi" is ff and i
+    let chars: Vec<char> = "ffi".chars().collect();
+    let ligature = substitution::ligature(&font, &chars).unwrap();
+    assert_eq!(ligature.chars, vec!['f', 'f']);
+}
+
+#[test]
+fn contextual_alternates_follow_the_neighbours() {
+    let r = |text: &str| layout(text, 0).glyphs[0].advance;
+    // r has more room before n, not before a
+    assert!(r("rn") > r("ra"));
+    assert_eq!(r("rm"), r("rn"));
+    assert_eq!(r("r"), r("ra"));
+}
+
+#[test]
+fn variants_depend_on_the_seed_only() {
+    let text = "tttttttttttttttt";
+    let strokes = |seed| {
+        layout(text, seed)
+            .glyphs
+            .iter()
+            .map(|glyph| glyph.strokes.len())
+            .collect::<Vec<_>>()
+    };
+
+    // Plain t has 2 strokes, the hooked one 3, both show up
+    let first = strokes(1);
+    assert!(first.contains(&2) && first.contains(&3));
+    assert_eq!(strokes(1), first);
+    assert_ne!(strokes(2), first);
+
+    // Without a seed it's always the plain t
+    let plain = TextLayout::new(0.0, 0.0, 1.0, 1.0).layout(&define_font(), text);
+    assert!(plain.glyphs.iter().all(|glyph| glyph.strokes.len() == 2));
+}
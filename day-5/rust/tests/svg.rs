@@ -0,0 +1,45 @@
+use day_5::drawing::{Drawing, DrawingOptions};
+use day_5::renderers::SvgDrawing;
+
+// The large arc and sweep flags of every arc path, in drawing order
+fn arc_flags(svg: &mut SvgDrawing) -> Vec<String> {
+    svg.render()
+        .lines()
+        .filter(|line| line.starts_with("<path d=\"M"))
+        .map(|line| line.split_whitespace().nth(6).unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn arc_flags_follow_the_sweep() {
+    let mut svg = SvgDrawing::with_page(DrawingOptions::default(), 200.0, 200.0, "white");
+    // Anticlockwise in drawing coordinates is clockwise once y points down
+    svg.arc(100.0, 100.0, 50.0, 0.0, 90.0);
+    svg.arc(100.0, 100.0, 50.0, 0.0, 270.0);
+    // Clockwise
+    svg.arc(100.0, 100.0, 50.0, 90.0, 0.0);
+    svg.arc(100.0, 100.0, 50.0, 270.0, 0.0);
+    // Half a turn, from a negative angle, is not a large arc
+    svg.arc(100.0, 100.0, 50.0, -90.0, 90.0);
+    assert_eq!(arc_flags(&mut svg), ["0,0", "1,0", "0,1", "1,1", "0,0"]);
+}
+
+#[test]
+fn arcs_start_and_end_on_the_flipped_circle() {
+    let mut svg = SvgDrawing::with_page(DrawingOptions::default(), 200.0, 200.0, "white");
+    svg.arc(100.0, 100.0, 50.0, 0.0, 90.0);
+    let out = svg.render();
+    let path = out.lines().find(|l| l.starts_with("<path")).unwrap();
+    let words: Vec<_> = path.split_whitespace().collect();
+    assert_eq!(words[2], "150,100");
+    let (x, y) = words[7].trim_end_matches('"').split_once(',').unwrap();
+    assert!((x.parse::<f64>().unwrap() - 100.0).abs() < 1e-9);
+    assert!((y.parse::<f64>().unwrap() - 50.0).abs() < 1e-9);
+
+    // A whole turn either way is a circle
+    let mut svg = SvgDrawing::with_page(DrawingOptions::default(), 200.0, 200.0, "white");
+    svg.arc(100.0, 50.0, 50.0, 360.0, 0.0);
+    let out = svg.render();
+    assert!(out.contains(r#"<circle cx="100" cy="150" r="50""#), "{out}");
+    assert!(!out.contains("<path"));
+}
@@ -0,0 +1,112 @@
+use day_5::layout::TextLayout;
+use day_5::letters::define_font;
+use day_5::transform::Transform;
+use day_5::types::{Pt, Stroke};
+
+fn close(a: Pt<f64>, b: Pt<f64>) -> bool {
+    (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9
+}
+
+fn circle() -> Stroke<f64> {
+    Stroke::Arc {
+        center: Pt(10.0, 0.0),
+        radius: 5.0,
+        start: 0.0,
+        end: 90.0,
+    }
+}
+
+#[test]
+fn composes_in_order() {
+    let t = Transform::scale(2.0, 2.0).then(Transform::translate(1.0, 0.0));
+    assert!(close(t.apply(Pt(1.0, 1.0)), Pt(3.0, 2.0)));
+    assert_eq!(
+        Transform::translate(1.0, 0.0) * Transform::scale(2.0, 2.0),
+        t
+    );
+
+    let r = Transform::rotate(90.0).around(Pt(1.0, 1.0));
+    assert!(close(r.apply(Pt(2.0, 1.0)), Pt(1.0, 2.0)));
+    let back = r.inverse().unwrap();
+    assert!(close(back.apply(Pt(1.0, 2.0)), Pt(2.0, 1.0)));
+}
+
+#[test]
+fn similarity_transforms_keep_arcs() {
+    let rotated = Transform::rotate(90.0).apply_stroke(&circle(), 0.1);
+    let Stroke::Arc {
+        center,
+        radius,
+        start,
+        end,
+    } = rotated
+    else {
+        panic!("rotated arcs stay arcs");
+    };
+    assert!(close(center, Pt(0.0, 10.0)));
+    assert_eq!((radius, start, end), (5.0, 90.0, 180.0));
+
+    // Mirroring keeps the end points but turns the sweep around
+    let mirrored = Transform::mirror_x().apply_stroke(&circle(), 0.1);
+    assert!(matches!(mirrored, Stroke::Arc { .. }));
+    assert!(close(mirrored.start_point(), Pt(-15.0, 0.0)));
+    assert!(close(mirrored.end_point(), Pt(-10.0, 5.0)));
+    assert!(mirrored.sweep() < 0.0);
+}
+
+#[test]
+fn non_uniform_scale_flattens_arcs_into_ellipses() {
+    let condensed = Transform::scale(0.5, 1.0).apply_stroke(&circle(), 0.01);
+    let Stroke::Polyline { points } = &condensed else {
+        panic!("condensed arcs are flattened");
+    };
+    assert!(close(points[0], Pt(7.5, 0.0)));
+    assert!(close(*points.last().unwrap(), Pt(5.0, 5.0)));
+    for p in points {
+        // (x / 2.5)^2 + (y / 5)^2 = 1 around the moved center
+        let e = ((p.0 - 5.0) / 2.5).powi(2) + (p.1 / 5.0).powi(2);
+        assert!((e - 1.0).abs() < 0.01, "{p:?}");
+    }
+}
+
+#[test]
+fn italic_layout_leans_glyphs_from_the_baseline() {
+    let font = define_font();
+    let upright = TextLayout::new(0.0, 0.0, 1.0, 1.0).layout(&font, "l");
+    let italic = TextLayout::new(0.0, 0.0, 1.0, 1.0)
+        .transform(Transform::skew_x(45.0))
+        .layout(&font, "l");
+
+    // 'l' is a line from the baseline to the ascender at 140, 56 units up
+    assert_eq!(upright.glyphs[0].strokes[0].start_point(), Pt(20.0, 0.0));
+    let stroke = &italic.glyphs[0].strokes[0];
+    assert!(close(stroke.start_point(), Pt(20.0, 0.0)));
+    assert!(close(stroke.end_point(), Pt(76.0, 56.0)));
+}
+
+#[test]
+fn condensed_layout_is_narrower() {
+    let font = define_font();
+    let normal = TextLayout::new(0.0, 0.0, 1.0, 1.0).layout(&font, "genurary");
+    let condensed = TextLayout::new(0.0, 0.0, 1.0, 1.0)
+        .transform(Transform::scale(0.6, 1.0))
+        .layout(&font, "genurary");
+
+    assert!((condensed.width - normal.width * 0.6).abs() < 1e-9);
+    for (c, n) in condensed.glyphs.iter().zip(&normal.glyphs) {
+        assert!((c.x - n.x * 0.6).abs() < 1e-9);
+        assert!((c.advance - n.advance * 0.6).abs() < 1e-9);
+    }
+
+    // Wrapping measures the condensed widths, so more fits on a line
+    let wrapped = |transform: Transform| {
+        TextLayout::new(0.0, 0.0, 1.0, 1.0)
+            .max_width(normal.width)
+            .transform(transform)
+            .layout(&font, "genurary genurary")
+            .lines
+            .len()
+    };
+    assert_eq!(wrapped(Transform::identity()), 2);
+    assert_eq!(wrapped(Transform::scale(0.4, 1.0)), 1);
+}
@@ -0,0 +1,15 @@
+use day_5::types::Font;
+use glyphs_macro::glyphs;
+
+fn main() {
+    let _: Font = glyphs! {
+        '\u{301}' {
+            line (45, 15) -> (65, 40)
+            anchor above (50, 0)
+        }
+        'o' {
+            anchor top (50, 100)
+            anchor top (50, 90)
+        }
+    };
+}
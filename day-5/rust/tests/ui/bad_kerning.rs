@@ -0,0 +1,11 @@
+use day_5::types::Font;
+use glyphs_macro::glyphs;
+
+fn main() {
+    let _: Font = glyphs! {
+        'r' { }
+        kern 'r' 'y' (-10)
+        kern 'r' 'y' (-20)
+        kern 'T' 'o' (-150)
+    };
+}
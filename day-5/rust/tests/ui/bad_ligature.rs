@@ -0,0 +1,16 @@
+use day_5::types::Font;
+use glyphs_macro::glyphs;
+
+fn main() {
+    let _: Font = glyphs! {
+        'f' { }
+        ligature 'f' {
+            line (0, 0) -> (100, 100)
+        }
+        ligature 'f' 'f' {
+            line (0, 0) -> (250, 100)
+        }
+        ligature 'f' 'f' { }
+        alternate 'q' before ('u') { }
+    };
+}
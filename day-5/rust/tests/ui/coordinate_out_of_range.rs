@@ -0,0 +1,16 @@
+use day_5::types::Font;
+use glyphs_macro::glyphs;
+
+fn main() {
+    let _: Font = glyphs! {
+        metrics {
+            ascender (140)
+            descender (-50)
+        }
+
+        'a' {
+            line (0, -60) -> (100, 140)
+            arc (150, 50) radius (20) from (0) -> (360)
+        }
+    };
+}
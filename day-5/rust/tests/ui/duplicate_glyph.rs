@@ -0,0 +1,13 @@
+use day_5::types::Font;
+use glyphs_macro::glyphs;
+
+fn main() {
+    let _: Font = glyphs! {
+        'a' {
+            line (0, 0) -> (100, 100)
+        }
+        'a' {
+            line (0, 100) -> (100, 0)
+        }
+    };
+}
@@ -0,0 +1,14 @@
+use day_5::types::Font;
+use glyphs_macro::glyphs;
+
+fn main() {
+    let _: Font = glyphs! {
+        metrics {
+            baseline (0)
+            x_height (120)
+            cap_height (100)
+            descender (10)
+            line_gap (-5)
+        }
+    };
+}
@@ -0,0 +1,11 @@
+use day_5::types::Font;
+use glyphs_macro::glyphs;
+
+fn main() {
+    let _: Font = glyphs! {
+        'o' {
+            arc (50, 50) radius (0) from (0) -> (360)
+            arc (50, 50) radius (-5) from (0) -> (360)
+        }
+    };
+}
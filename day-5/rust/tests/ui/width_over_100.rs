@@ -0,0 +1,11 @@
+use day_5::types::Font;
+use glyphs_macro::glyphs;
+
+fn main() {
+    let _: Font = glyphs! {
+        'l' {
+            line (50, 0) -> (50, 100)
+            restrict_width (120)
+        }
+    };
+}